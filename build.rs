@@ -51,13 +51,13 @@ impl Default for LongHelp {
 
 impl Display for LongHelp {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "{}\n", self.pkg_version)?;
-		write!(f, "commit: {}\n", self.commit)?;
-		write!(f, "branch: {}\n", self.branch)?;
-		write!(f, "tag: {}\n", self.tag)?;
-		write!(f, "clean: {}\n", self.clean)?;
-		write!(f, "profile: {}\n", self.build_profile)?;
-		write!(f, "rustc: {}\n", self.rustc_version)
+		writeln!(f, "{}", self.pkg_version)?;
+		writeln!(f, "commit: {}", self.commit)?;
+		writeln!(f, "branch: {}", self.branch)?;
+		writeln!(f, "tag: {}", self.tag)?;
+		writeln!(f, "clean: {}", self.clean)?;
+		writeln!(f, "profile: {}", self.build_profile)?;
+		writeln!(f, "rustc: {}", self.rustc_version)
 	}
 }
 
@@ -105,13 +105,11 @@ impl LongHelp {
 
 		// tag info
 		let all_tags = repo.tag_names(None)?;
-		for tag in all_tags.iter() {
-			if let Some(tag) = tag {
-				let tag_obj = repo.find_reference(&format!("refs/tags/{}", tag))?.peel_to_commit()?;
-				if tag_obj.id() == head_commit {
-					self.tag = tag.to_string();
-					break;
-				}
+		for tag in all_tags.iter().flatten() {
+			let tag_obj = repo.find_reference(&format!("refs/tags/{}", tag))?.peel_to_commit()?;
+			if tag_obj.id() == head_commit {
+				self.tag = tag.to_string();
+				break;
 			}
 		}
 		Ok(())
@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::bail;
+use jsonnet::JsonnetVm;
+
+use crate::Resume;
+
+pub const DEFAULT_MAX_STACK: u32 = 200;
+pub const DEFAULT_MAX_TRACE: u32 = 20;
+
+/// Settings for the Jsonnet VM that evaluates a resume
+#[derive(Debug, Clone)]
+pub struct JsonnetOptions {
+	/// External variables, read with `std.extVar`
+	pub ext_strs: Vec<(String, String)>,
+	/// Top-level arguments, passed when the source evaluates to a function
+	pub tla_strs: Vec<(String, String)>,
+	/// Searched by `import` after the directory of the importing file
+	pub library_paths: Vec<PathBuf>,
+	pub max_stack: u32,
+	pub max_trace: Option<u32>,
+}
+
+impl Default for JsonnetOptions {
+	fn default() -> Self {
+		JsonnetOptions {
+			ext_strs: Vec::new(),
+			tla_strs: Vec::new(),
+			library_paths: Vec::new(),
+			max_stack: DEFAULT_MAX_STACK,
+			max_trace: Some(DEFAULT_MAX_TRACE),
+		}
+	}
+}
+
+impl JsonnetOptions {
+	fn vm(&self) -> JsonnetVm {
+		let mut vm = JsonnetVm::new();
+		vm.max_trace(self.max_trace);
+		vm.max_stack(self.max_stack);
+		for (key, value) in &self.ext_strs {
+			vm.ext_var(key, value);
+		}
+		for (key, value) in &self.tla_strs {
+			vm.tla_var(key, value);
+		}
+		for path in &self.library_paths {
+			vm.jpath_add(path);
+		}
+		vm
+	}
+
+	/// Evaluates the file at `path` into a JSON string. Relative imports resolve against its directory
+	fn evaluate_file(&self, path: &Path) -> anyhow::Result<String> {
+		let mut vm = self.vm();
+		let output = vm.evaluate_file(path);
+
+		match output {
+			Ok(val) => {
+				log::debug!("jsonnet out:{}", val);
+				Ok(val.to_string())
+			}
+			Err(err) => {
+				log::error!("jsonnet err: {}", err);
+				bail!("{}", err)
+			}
+		}
+	}
+
+	/// Evaluates `data` into a JSON string. `name` shows up in errors, and relative imports resolve against the
+	/// current directory
+	fn evaluate_snippet(&self, name: &str, data: &str) -> anyhow::Result<String> {
+		let mut vm = self.vm();
+		let output = vm.evaluate_snippet(name, data);
+
+		match output {
+			Ok(val) => {
+				log::debug!("jsonnet out:{}", val);
+				Ok(val.to_string())
+			}
+			Err(err) => {
+				log::error!("jsonnet err: {}", err);
+				bail!("{}", err)
+			}
+		}
+	}
+}
+
+impl Resume {
+	/// Evaluates Jsonnet source held in memory, e.g. read from stdin
+	pub fn from_jsonnet_buffer(data: &str, opts: &JsonnetOptions) -> anyhow::Result<Self> {
+		let val = opts.evaluate_snippet("<buffer>", data)?;
+		Ok(json5::from_str(&val)?)
+	}
+
+	pub fn from_jsonnet_file(path: &Path, opts: &JsonnetOptions) -> anyhow::Result<Self> {
+		let val = opts.evaluate_file(path)?;
+		Ok(json5::from_str(&val)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn ext_strs_are_external_variables() {
+		let opts = JsonnetOptions {
+			ext_strs: vec![(String::from("name"), String::from("Jane"))],
+			..Default::default()
+		};
+		let resume = Resume::from_jsonnet_buffer("{ basics: { name: std.extVar('name') } }", &opts).unwrap();
+		assert_eq!(resume.basics.name.as_deref(), Some("Jane"));
+	}
+
+	#[test]
+	fn tla_strs_are_function_arguments() {
+		let opts = JsonnetOptions {
+			tla_strs: vec![(String::from("label"), String::from("Engineer"))],
+			..Default::default()
+		};
+		let resume = Resume::from_jsonnet_buffer("function(label) { basics: { label: label } }", &opts).unwrap();
+		assert_eq!(resume.basics.label.as_deref(), Some("Engineer"));
+	}
+
+	#[test]
+	fn imports_resolve_through_library_paths() {
+		let dir = std::env::temp_dir().join(format!("rsb-jsonnet-{}", std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		std::fs::write(
+			dir.join("basics.libsonnet"),
+			"{ name: 'Jane', email: 'jane@example.com' }",
+		)
+		.unwrap();
+
+		let opts = JsonnetOptions {
+			library_paths: vec![dir.clone()],
+			..Default::default()
+		};
+		let resume = Resume::from_jsonnet_buffer("{ basics: import 'basics.libsonnet' }", &opts);
+		std::fs::remove_dir_all(dir).unwrap();
+
+		let resume = resume.unwrap();
+		assert_eq!(resume.basics.name.as_deref(), Some("Jane"));
+		assert_eq!(resume.basics.email.as_deref(), Some("jane@example.com"));
+	}
+}
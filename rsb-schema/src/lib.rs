@@ -1,15 +1,24 @@
+#[cfg(feature = "jsonnet")]
+mod jsonnet_vm;
 mod model;
 
 use anyhow::{anyhow, bail};
-use std::{fs::read_to_string, path::PathBuf};
+use std::{fs::read_to_string, path::PathBuf, str::FromStr};
 
+#[cfg(feature = "jsonnet")]
+pub use jsonnet_vm::{JsonnetOptions, DEFAULT_MAX_STACK, DEFAULT_MAX_TRACE};
 pub use model::*;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DataType {
 	Json5,
 	Yaml,
 	#[cfg(feature = "ron")]
 	Ron,
+	#[cfg(feature = "toml")]
+	Toml,
+	#[cfg(feature = "jsonnet")]
+	Jsonnet,
 }
 
 enum FileType {
@@ -19,6 +28,8 @@ enum FileType {
 	Ron,
 	#[cfg(feature = "jsonnet")]
 	Jsonnet,
+	#[cfg(feature = "toml")]
+	Toml,
 }
 
 impl TryFrom<FileType> for DataType {
@@ -30,11 +41,93 @@ impl TryFrom<FileType> for DataType {
 			#[cfg(feature = "ron")]
 			FileType::Ron => Ok(DataType::Ron),
 			#[cfg(feature = "jsonnet")]
-			FileType::Jsonnet => bail!("Jsonnet can not be inited from buffer"),
+			FileType::Jsonnet => Ok(DataType::Jsonnet),
+			#[cfg(feature = "toml")]
+			FileType::Toml => Ok(DataType::Toml),
 		}
 	}
 }
 
+impl FromStr for DataType {
+	type Err = anyhow::Error;
+	fn from_str(value: &str) -> anyhow::Result<Self> {
+		match value {
+			"json" | "json5" => Ok(DataType::Json5),
+			"yaml" | "yml" => Ok(DataType::Yaml),
+			#[cfg(feature = "ron")]
+			"ron" => Ok(DataType::Ron),
+			#[cfg(feature = "toml")]
+			"toml" => Ok(DataType::Toml),
+			#[cfg(feature = "jsonnet")]
+			"jsonnet" => Ok(DataType::Jsonnet),
+			_ => bail!("unknown data type {}", value),
+		}
+	}
+}
+
+impl DataType {
+	/// Data type for the extension of `path`
+	pub fn from_path(path: &PathBuf) -> anyhow::Result<Self> {
+		DataType::try_from(path.file_type()?)
+	}
+
+	/// Guesses the data type from the first line that is not blank or a comment. YAML is the fallback, since it
+	/// accepts the most
+	pub fn sniff(data: &str) -> Self {
+		let first = data
+			.lines()
+			.map(str::trim)
+			.find(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with("//"))
+			.unwrap_or_default();
+
+		#[cfg(feature = "jsonnet")]
+		if first.starts_with("local ") {
+			return DataType::Jsonnet;
+		}
+
+		match first.chars().next() {
+			// a resume is an object, so a JSON document can not start with an array
+			Some('{') => DataType::Json5,
+			#[cfg(feature = "ron")]
+			Some('(') => DataType::Ron,
+			#[cfg(feature = "toml")]
+			_ if is_toml_start(first) => DataType::Toml,
+			_ => DataType::Yaml,
+		}
+	}
+}
+
+/// Top-level keys of a resume. A TOML resume starts with one of these, as a table or as a key
+#[cfg(feature = "toml")]
+const RESUME_KEYS: [&str; 14] = [
+	"$schema",
+	"awards",
+	"basics",
+	"certificates",
+	"education",
+	"interests",
+	"languages",
+	"meta",
+	"projects",
+	"publications",
+	"references",
+	"skills",
+	"volunteer",
+	"work",
+];
+
+/// `[basics]`, `[[work]]` or `basics.name = ...`, where YAML would use `basics:`
+#[cfg(feature = "toml")]
+fn is_toml_start(line: &str) -> bool {
+	let key = match line.strip_prefix('[') {
+		Some(header) => header.trim_start_matches('[').split_once(']').map(|(key, _)| key),
+		None => line.split_once('=').map(|(key, _)| key),
+	};
+	key.and_then(|key| key.split('.').next())
+		.map(|key| key.trim().trim_matches(['"', '\'']))
+		.is_some_and(|key| RESUME_KEYS.contains(&key))
+}
+
 trait PathFileType {
 	fn file_type(&self) -> anyhow::Result<FileType>;
 }
@@ -53,6 +146,8 @@ impl PathFileType for &PathBuf {
 			"ron" => Ok(FileType::Ron),
 			#[cfg(feature = "jsonnet")]
 			"jsonnet" => Ok(FileType::Jsonnet),
+			#[cfg(feature = "toml")]
+			"toml" => Ok(FileType::Toml),
 			_ => bail!("unknown extension {} for file {:?}", ext, self),
 		}
 	}
@@ -65,6 +160,24 @@ impl Resume {
 			DataType::Yaml => Ok(serde_yml::from_str(data)?),
 			#[cfg(feature = "ron")]
 			DataType::Ron => Ok(ron::from_str(data)?),
+			#[cfg(feature = "toml")]
+			DataType::Toml => Ok(toml::from_str(data)?),
+			#[cfg(feature = "jsonnet")]
+			DataType::Jsonnet => Resume::from_jsonnet_buffer(data, &JsonnetOptions::default()),
+		}
+	}
+
+	/// Serializes into `data_type`. JSON5 and Jsonnet are written as plain JSON, which both accept
+	pub fn to_buffer(&self, data_type: DataType) -> anyhow::Result<String> {
+		match data_type {
+			DataType::Json5 => Ok(serde_json::to_string_pretty(self)?),
+			DataType::Yaml => Ok(serde_yml::to_string(self)?),
+			#[cfg(feature = "ron")]
+			DataType::Ron => Ok(ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?),
+			#[cfg(feature = "toml")]
+			DataType::Toml => Ok(toml::to_string_pretty(self)?),
+			#[cfg(feature = "jsonnet")]
+			DataType::Jsonnet => Ok(serde_json::to_string_pretty(self)?),
 		}
 	}
 
@@ -73,29 +186,68 @@ impl Resume {
 
 		match file_type {
 			#[cfg(feature = "jsonnet")]
-			FileType::Jsonnet => {
-				let mut vm = jsonnet::JsonnetVm::new();
-				vm.max_trace(Some(20));
-				vm.max_stack(200);
-
-				let output = vm.evaluate_file(path);
-
-				match output {
-					Ok(val) => {
-						log::debug!("jsonnet out:{}", val);
-						Ok(json5::from_str(&val)?)
-					}
-					Err(err) => {
-						log::error!("jsonnet err: {}", err);
-						bail!("{}", err)
-					}
-				}
-			}
+			FileType::Jsonnet => Resume::from_jsonnet_file(path, &JsonnetOptions::default()),
 			_ => {
-				let inp = read_to_string(&path)?;
+				let inp = read_to_string(path)?;
 				let inp_str = inp.as_str();
 				Resume::from_buffer(inp_str, DataType::try_from(file_type)?)
 			}
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sniff_json_and_yaml() {
+		assert_eq!(DataType::sniff("// comment\n\n  {\"basics\": {}}"), DataType::Json5);
+		assert_eq!(DataType::sniff("# comment\nbasics:\n  name: A"), DataType::Yaml);
+		assert_eq!(DataType::sniff("---\nwork: []"), DataType::Yaml);
+		assert_eq!(DataType::sniff(""), DataType::Yaml);
+	}
+
+	#[cfg(feature = "toml")]
+	#[test]
+	fn sniff_toml_needs_a_resume_key() {
+		assert_eq!(DataType::sniff("[basics]\nname = \"A\""), DataType::Toml);
+		assert_eq!(DataType::sniff("[[work]]\nname = \"B\""), DataType::Toml);
+		assert_eq!(DataType::sniff("basics.name = \"A\""), DataType::Toml);
+		assert_eq!(DataType::sniff("\"$schema\" = \"https://example.com\""), DataType::Toml);
+		assert_eq!(DataType::sniff("name = 1"), DataType::Yaml);
+		assert_eq!(DataType::sniff("[1, 2]"), DataType::Yaml);
+		assert_eq!(DataType::sniff("[basics"), DataType::Yaml);
+	}
+
+	#[cfg(feature = "toml")]
+	#[test]
+	fn toml_resume() {
+		let data = r#"
+[basics]
+name = "Jane"
+
+[[work]]
+name = "Acme"
+startDate = "2020-03"
+endDate = "2022-01-15"
+"#;
+		let resume = Resume::from_buffer(data, DataType::Toml).unwrap();
+		assert_eq!(resume.basics.name.as_deref(), Some("Jane"));
+		assert_eq!(resume.work[0].name.as_deref(), Some("Acme"));
+		assert_eq!(resume.work[0].start_date, Some(Iso8601::YearMonth(2020, 3)));
+		assert_eq!(resume.work[0].end_date, Some(Iso8601::Full(2022, 1, 15)));
+
+		let path = std::env::temp_dir().join(format!("rsb-schema-{}.toml", std::process::id()));
+		std::fs::write(&path, data).unwrap();
+		let from_file = Resume::from_file(&path);
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(from_file.unwrap().work[0].start_date, Some(Iso8601::YearMonth(2020, 3)));
+	}
+
+	#[cfg(feature = "ron")]
+	#[test]
+	fn sniff_ron() {
+		assert_eq!(DataType::sniff("(basics: (name: Some(\"A\")))"), DataType::Ron);
+	}
+}
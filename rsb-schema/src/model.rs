@@ -1,7 +1,7 @@
 use std::{
-	cell::LazyCell,
 	fmt::{self, Display},
 	str::FromStr,
+	sync::LazyLock,
 };
 
 use anyhow::bail;
@@ -9,10 +9,10 @@ use chrono::NaiveDate;
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-const C: LazyCell<Regex> = LazyCell::new(|| Regex::new(r"^[0-9]{4}(-[0-9]{2})?(-[0-9]{2})?$").unwrap());
+static C: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[0-9]{4}(-[0-9]{2})?(-[0-9]{2})?$").unwrap());
 
 #[doc = "Similar to the standard date type, but each section after the year is optional. e.g. 2014-06-29 or 2023-04"]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Iso8601 {
 	Year(u16),
 	YearMonth(u16, u8),
@@ -69,8 +69,8 @@ impl Serialize for Iso8601 {
 	{
 		let ser_val = match self {
 			Iso8601::Year(y) => format!("{}", y),
-			Iso8601::YearMonth(y, m) => format!("{}-{}", y, m),
-			Iso8601::Full(y, m, d) => format!("{}-{}-{}", y, m, d),
+			Iso8601::YearMonth(y, m) => format!("{}-{:02}", y, m),
+			Iso8601::Full(y, m, d) => format!("{}-{:02}-{:02}", y, m, d),
 		};
 
 		serializer.serialize_str(&ser_val)
@@ -0,0 +1,28 @@
+//! Pieces of a publication citation shared by the HTML and outline renderers
+
+use rsb_schema::Iso8601;
+
+use crate::dates::month_name;
+
+/// Appends `sep` unless `text` already ends with terminal punctuation
+pub fn terminated(text: &str, sep: char) -> String {
+	match text.ends_with(['.', '?', '!']) {
+		true => text.to_string(),
+		false => format!("{}{}", text, sep),
+	}
+}
+
+pub fn apa_date(date: &Iso8601) -> String {
+	match *date {
+		Iso8601::Year(y) => format!("{}", y),
+		Iso8601::YearMonth(y, m) => format!("{}, {}", y, month_name(m)),
+		Iso8601::Full(y, m, d) => format!("{}, {} {}", y, month_name(m), d),
+	}
+}
+
+pub fn ieee_date(date: &Iso8601) -> String {
+	match *date {
+		Iso8601::Year(y) => format!("{}", y),
+		Iso8601::YearMonth(y, m) | Iso8601::Full(y, m, _) => format!("{} {}", month_name(m), y),
+	}
+}
@@ -0,0 +1,64 @@
+use hypertext::{html_elements, maud, GlobalAttributes, RenderIterator, Renderable};
+use rsb_schema::AwardsItem;
+
+use super::common::DateRenderer;
+
+struct AwardsItemRenderer(AwardsItem);
+impl Renderable for AwardsItemRenderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.title.is_none() {
+			log::warn!("No title in AwardsItem {:?}", self.0);
+		}
+
+		maud! {
+			div .item {
+				div .item-header {
+					@if let Some(title) = self.0.title {
+						span .item-header-title {
+							(title)
+						}
+					}
+					@if let Some(awarder) = self.0.awarder {
+						br;
+						span .item-header-subtitle {
+							(awarder)
+						}
+					}
+				}
+				div .item-details {
+					@if let Some(date) = self.0.date {
+						span .item-details-title {
+							(DateRenderer(date))
+						}
+					}
+				}
+				div .clearfix {}
+				@if let Some(summary) = self.0.summary {
+					div .item-summary {
+						(summary)
+					}
+				}
+			}
+		}
+		.render_to(output);
+	}
+}
+
+pub struct Renderer(pub Vec<AwardsItem>);
+impl Renderable for Renderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.is_empty() {
+			return;
+		}
+
+		maud! {
+			section #awards {
+				h2 {
+					"Awards"
+				}
+				(self.0.into_iter().map(AwardsItemRenderer).render_all())
+			}
+		}
+		.render_to(output);
+	}
+}
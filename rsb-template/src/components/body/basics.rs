@@ -1,5 +1,7 @@
 use hypertext::{html_elements, maud, GlobalAttributes, RenderIterator, Renderable};
-use rsb_schema::{Basics, BasicsProfilesItem};
+use rsb_schema::{Basics, BasicsLocation, BasicsProfilesItem};
+
+use crate::text;
 
 struct SpanWrapper<R: Renderable>(R);
 impl<R: Renderable> Renderable for SpanWrapper<R> {
@@ -22,7 +24,7 @@ impl Renderable for EmailRenderer {
 		href.push_str(self.0.as_str());
 
 		SpanWrapper(maud!(
-			a href=(href) {
+			a .u-email href=(href) {
 				(self.0)
 			}
 		))
@@ -34,7 +36,7 @@ struct UrlRenderer(String);
 impl Renderable for UrlRenderer {
 	fn render_to(self, output: &mut String) {
 		SpanWrapper(maud! {
-			a href=(self.0.as_str()) {
+			a .u-url href=(self.0.as_str()) {
 				(self.0)
 			}
 		})
@@ -48,33 +50,65 @@ impl Renderable for ImageRenderer {
 		log::warn!("embedding an image is experimental. YMMV");
 		maud! {
 			div .contact .centered {
-				img #basics-image alt="Profile image" src=(self.0);
+				img #basics-image .u-photo alt="Profile image" src=(self.0);
 			}
 		}
 		.render_to(output);
 	}
 }
 
-struct ProfileItemRenderer(BasicsProfilesItem);
-impl Renderable for ProfileItemRenderer {
+struct LocationRenderer(BasicsLocation);
+impl Renderable for LocationRenderer {
 	fn render_to(self, output: &mut String) {
-		if self.0.username.is_some() {
-			log::warn!("username ignored in profile {:?}", self.0);
+		let parts: Vec<(&str, String)> = [
+			("p-locality", self.0.city),
+			("p-region", self.0.region),
+			("p-country-name", self.0.country_code),
+		]
+		.into_iter()
+		.filter_map(|(class, part)| part.map(|part| (class, part)))
+		.filter(|(_, part)| !part.trim().is_empty())
+		.collect();
+
+		if parts.is_empty() {
+			return;
 		}
 
-		let url = match &self.0.url {
-			Some(s) => s,
-			None => {
-				log::error!("profile url empty for {:?}. Skipping", self.0);
-				return;
+		let last = parts.len() - 1;
+		SpanWrapper(maud! {
+			span .p-adr .h-adr {
+				@for (i, (class, part)) in parts.into_iter().enumerate() {
+					span class=(class) {
+						(part)
+					}
+					@if i < last {
+						", "
+					}
+				}
 			}
-		};
+		})
+		.render_to(output);
+	}
+}
+
+struct ProfileItemRenderer(BasicsProfilesItem);
+impl Renderable for ProfileItemRenderer {
+	fn render_to(self, output: &mut String) {
+		let text = text::profile(self.0.network, self.0.username);
+
+		if text.is_none() && self.0.url.is_none() {
+			log::error!("profile has no network, username or url. Skipping");
+			return;
+		}
 
 		maud! {
 			div .contact .centered {
 				span .vertical-separator {
-					a href=(url) {
-						(url)
+					@match (text, self.0.url) {
+						(Some(text), Some(url)) => a .u-url href=(url) { (text) }
+						(None, Some(url)) => a .u-url href=(url.as_str()) { (url) }
+						(Some(text), None) => (text)
+						(None, None) => {}
 					}
 				}
 			}
@@ -87,7 +121,7 @@ struct NameRenderer(String);
 impl Renderable for NameRenderer {
 	fn render_to(self, output: &mut String) {
 		maud! {
-			h1 #basics-name-heading {
+			h1 #basics-name-heading .p-name {
 				(self.0)
 			}
 		}
@@ -103,14 +137,15 @@ impl Renderable for Renderer {
 		}
 
 		maud! {
-			section #basics {
+			section #basics .p-contact .h-card {
 				(self.0.name.map(NameRenderer))
-				(self.0.label.map(SpanWrapper))
+				(self.0.label.map(|label| SpanWrapper(maud! { span .p-job-title { (label) } })))
 				(self.0.image.map(ImageRenderer))
 				(self.0.email.map(EmailRenderer))
-				(self.0.phone.map(SpanWrapper))
+				(self.0.phone.map(|phone| SpanWrapper(maud! { span .p-tel { (phone) } })))
 				(self.0.url.map(UrlRenderer))
-				(self.0.summary.map(SpanWrapper))
+				(LocationRenderer(self.0.location))
+				(self.0.summary.map(|summary| SpanWrapper(maud! { span .p-note { (summary) } })))
 				(self.0.profiles.into_iter().map(ProfileItemRenderer).render_all())
 			}
 		}
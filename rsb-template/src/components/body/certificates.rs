@@ -0,0 +1,57 @@
+use hypertext::{html_elements, maud, GlobalAttributes, RenderIterator, Renderable};
+use rsb_schema::CertificatesItem;
+
+use super::common::{DateRenderer, LinkRenderer};
+
+struct CertificatesItemRenderer(CertificatesItem);
+impl Renderable for CertificatesItemRenderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.name.is_none() {
+			log::warn!("No name in CertificatesItem {:?}", self.0);
+		}
+
+		maud! {
+			div .item {
+				div .item-header {
+					span .item-header-title {
+						(LinkRenderer(self.0.name, self.0.url))
+					}
+					@if let Some(issuer) = self.0.issuer {
+						br;
+						span .item-header-subtitle {
+							(issuer)
+						}
+					}
+				}
+				div .item-details {
+					@if let Some(date) = self.0.date {
+						span .item-details-title {
+							(DateRenderer(date))
+						}
+					}
+				}
+				div .clearfix {}
+			}
+		}
+		.render_to(output);
+	}
+}
+
+pub struct Renderer(pub Vec<CertificatesItem>);
+impl Renderable for Renderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.is_empty() {
+			return;
+		}
+
+		maud! {
+			section #certificates {
+				h2 {
+					"Certificates"
+				}
+				(self.0.into_iter().map(CertificatesItemRenderer).render_all())
+			}
+		}
+		.render_to(output);
+	}
+}
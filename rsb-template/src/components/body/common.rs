@@ -0,0 +1,80 @@
+use hypertext::{html_elements, maud, GlobalAttributes, Renderable};
+use rsb_schema::Iso8601;
+
+use crate::dates::{format_date, format_range, iso_date, PRESENT};
+
+pub struct DateRenderer(pub Iso8601);
+impl Renderable for DateRenderer {
+	fn render_to(self, output: &mut String) {
+		format_date(&self.0).render_to(output);
+	}
+}
+
+/// Renders `start - end`, with a missing end date shown as an open range
+pub struct DateRangeRenderer(pub Option<Iso8601>, pub Option<Iso8601>);
+impl Renderable for DateRangeRenderer {
+	fn render_to(self, output: &mut String) {
+		format_range(self.0.as_ref(), self.1.as_ref()).render_to(output);
+	}
+}
+
+/// Like [`DateRangeRenderer`], but marks the dates up as `dt-start`/`dt-end` of an enclosing h-event
+pub struct EventDateRangeRenderer(pub Option<Iso8601>, pub Option<Iso8601>);
+impl Renderable for EventDateRangeRenderer {
+	fn render_to(self, output: &mut String) {
+		let start = self.0.map(|date| (iso_date(&date), format_date(&date)));
+		let end = self.1.map(|date| (iso_date(&date), format_date(&date)));
+
+		maud! {
+			@if let Some((datetime, text)) = start {
+				time .dt-start datetime=(datetime) {
+					(text)
+				}
+				" - "
+				@if end.is_none() {
+					(PRESENT)
+				}
+			}
+			@if let Some((datetime, text)) = end {
+				time .dt-end datetime=(datetime) {
+					(text)
+				}
+			}
+		}
+		.render_to(output);
+	}
+}
+
+/// Renders `text` as a link to `url`, falling back to whichever one is present
+pub struct LinkRenderer(pub Option<String>, pub Option<String>);
+impl Renderable for LinkRenderer {
+	fn render_to(self, output: &mut String) {
+		match (self.0, self.1) {
+			(Some(text), Some(url)) => maud! { a href=(url) { (text) } }.render_to(output),
+			(Some(text), None) => text.render_to(output),
+			(None, Some(url)) => maud! { a href=(url.as_str()) { (url) } }.render_to(output),
+			(None, None) => {}
+		}
+	}
+}
+
+/// Renders a list of keywords as tags
+pub struct KeywordsRenderer(pub Vec<String>);
+impl Renderable for KeywordsRenderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.is_empty() {
+			return;
+		}
+
+		maud! {
+			div .keywords {
+				@for keyword in self.0 {
+					span .keyword {
+						(keyword)
+					}
+				}
+			}
+		}
+		.render_to(output);
+	}
+}
@@ -1,64 +1,64 @@
 use hypertext::{html_elements, maud, GlobalAttributes, RenderIterator, Renderable};
-use rsb_schema::{EducationItem, Iso8601};
+use rsb_schema::EducationItem;
 
-struct YearOnlyDateRenderer(Iso8601);
-impl Renderable for YearOnlyDateRenderer {
-	fn render_to(self, output: &mut String) {
-		let year = match self.0 {
-			Iso8601::Full(y, ..) => y,
-			Iso8601::Year(y) => y,
-			Iso8601::YearMonth(y, _) => y,
-		};
-
-		year.render_to(output);
-	}
-}
+use super::common::{EventDateRangeRenderer, LinkRenderer};
+use crate::text;
 
 struct EducationItemRenderer(EducationItem);
 impl Renderable for EducationItemRenderer {
 	fn render_to(self, output: &mut String) {
-		let study_type = match self.0.study_type {
-			Some(ref a) => a,
-			None => {
-				log::warn!("No studyType in EducationItem {:?}. Skipping render", self.0);
-				return;
-			}
-		};
-
-		let area = match self.0.area {
-			Some(ref a) => a,
-			None => {
-				log::warn!("No area in EducationItem {:?}. Skipping render", self.0);
-				return;
-			}
-		};
-
-		let institution = match self.0.institution {
-			Some(ref a) => a,
-			None => {
-				log::warn!("No institution in EducationItem {:?}. Skipping render", self.0);
-				return;
-			}
-		};
-
-		if self.0.url.is_some() {
-			log::warn!("Ignoring url in EducationItem {:?}", self.0);
-		}
-		if self.0.start_date.is_some() {
-			log::warn!("Ignoring startDate in EducationItem {:?}", self.0);
-		}
-		if self.0.score.is_some() {
-			log::warn!("Ignoring score in EducationItem {:?}", self.0);
+		if self.0.study_type.is_none() && self.0.area.is_none() && self.0.institution.is_none() {
+			log::warn!("No studyType, area or institution in EducationItem {:?}", self.0);
 		}
 
-		if self.0.courses.len() != 0 {
-			log::warn!("Ignoring courses in EducationItem {:?}", self.0);
-		}
+		// the degree is the title when known, otherwise the institution takes its place
+		let title = text::degree(self.0.study_type, self.0.area);
+		let institution = self.0.institution;
+		let has_dates = self.0.start_date.is_some() || self.0.end_date.is_some();
+
 		maud! {
-			li {
-				(study_type) " in " (area) " from " (institution)
-				@if let Some(end_date) = self.0.end_date {
-					" (" (YearOnlyDateRenderer(end_date)) ")"
+			div .item .p-education .h-event {
+				div .item-header {
+					@if let Some(title) = title {
+						span .item-header-title .p-name {
+							(title)
+						}
+						@if institution.is_some() || self.0.url.is_some() {
+							br;
+							span .item-header-subtitle .p-location {
+								(LinkRenderer(institution, self.0.url))
+							}
+						}
+					} @else {
+						span .item-header-title .p-name {
+							(LinkRenderer(institution, self.0.url))
+						}
+					}
+				}
+				div .item-details {
+					@if has_dates {
+						span .item-details-title {
+							(EventDateRangeRenderer(self.0.start_date, self.0.end_date))
+						}
+					}
+					@if let Some(score) = self.0.score {
+						@if has_dates {
+							br;
+						}
+						span .score {
+							"GPA: " (score)
+						}
+					}
+				}
+				div .clearfix {}
+				@if !self.0.courses.is_empty() {
+					ul .courses {
+						@for course in self.0.courses {
+							li {
+								(course)
+							}
+						}
+					}
 				}
 			}
 		}
@@ -69,11 +69,16 @@ impl Renderable for EducationItemRenderer {
 pub struct Renderer(pub Vec<EducationItem>);
 impl Renderable for Renderer {
 	fn render_to(self, output: &mut String) {
+		if self.0.is_empty() {
+			return;
+		}
+
 		maud! {
 			section #education {
-				ul {
-					(self.0.into_iter().map(EducationItemRenderer).render_all())
+				h2 {
+					"Education"
 				}
+				(self.0.into_iter().map(EducationItemRenderer).render_all())
 			}
 		}
 		.render_to(output);
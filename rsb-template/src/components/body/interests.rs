@@ -0,0 +1,47 @@
+use hypertext::{html_elements, maud, GlobalAttributes, RenderIterator, Renderable};
+use rsb_schema::InterestsItem;
+
+use super::common::KeywordsRenderer;
+
+struct InterestsItemRenderer(InterestsItem);
+impl Renderable for InterestsItemRenderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.name.is_none() {
+			log::warn!("No name in InterestsItem {:?}", self.0);
+		}
+
+		maud! {
+			div .item {
+				div .item-header {
+					@if let Some(name) = self.0.name {
+						span .item-header-title {
+							(name)
+						}
+					}
+				}
+				div .clearfix {}
+				(KeywordsRenderer(self.0.keywords))
+			}
+		}
+		.render_to(output);
+	}
+}
+
+pub struct Renderer(pub Vec<InterestsItem>);
+impl Renderable for Renderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.is_empty() {
+			return;
+		}
+
+		maud! {
+			section #interests {
+				h2 {
+					"Interests"
+				}
+				(self.0.into_iter().map(InterestsItemRenderer).render_all())
+			}
+		}
+		.render_to(output);
+	}
+}
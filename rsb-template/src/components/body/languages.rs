@@ -0,0 +1,51 @@
+use hypertext::{html_elements, maud, GlobalAttributes, RenderIterator, Renderable};
+use rsb_schema::LanguagesItem;
+
+struct LanguagesItemRenderer(LanguagesItem);
+impl Renderable for LanguagesItemRenderer {
+	fn render_to(self, output: &mut String) {
+		let language = match self.0.language {
+			Some(ref l) => l,
+			None => {
+				log::warn!("No language in LanguagesItem {:?}. Skipping render", self.0);
+				return;
+			}
+		};
+
+		maud! {
+			li {
+				span .language {
+					(language)
+				}
+				@if let Some(fluency) = &self.0.fluency {
+					" - "
+					span .fluency {
+						(fluency)
+					}
+				}
+			}
+		}
+		.render_to(output);
+	}
+}
+
+pub struct Renderer(pub Vec<LanguagesItem>);
+impl Renderable for Renderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.is_empty() {
+			return;
+		}
+
+		maud! {
+			section #languages {
+				h2 {
+					"Languages"
+				}
+				ul {
+					(self.0.into_iter().map(LanguagesItemRenderer).render_all())
+				}
+			}
+		}
+		.render_to(output);
+	}
+}
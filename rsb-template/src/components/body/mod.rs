@@ -1,61 +1,64 @@
+mod awards;
 mod basics;
-// mod awards;
-// mod certificates;
+mod certificates;
+mod common;
 mod education;
-// mod interests;
-// mod languages;
-// mod projects;
-// mod publications;
-// mod references;
-// mod skills;
-// mod volunteer;
-// mod work;
+mod interests;
+mod languages;
+mod projects;
+mod publications;
+mod references;
+mod skills;
+mod volunteer;
+mod work;
 
 use hypertext::{html_elements, maud, GlobalAttributes, Renderable};
 use rsb_schema::Resume;
 
-pub struct Body(pub Resume);
+use crate::Options;
+
+pub struct Body(pub Resume, pub Options);
 impl Renderable for Body {
 	fn render_to(self, output: &mut String) {
 		maud! {
 			body {
-				div #resume .container {
+				div #resume .container .h-resume {
 					div #basics-section .section {
 						(basics::Renderer(self.0.basics))
 					}
 					div #education-section .section {
 						(education::Renderer(self.0.education))
 					}
-					// div #work-section .section {
-					// 	(self.0.work)
-					// }
-					// div #publications-section .section {
-					// 	(self.0.publications)
-					// }
-					// div #projects-section .section {
-					// 	(self.0.projects)
-					// }
-					// div #skills-section .section {
-					// 	(self.0.skills)
-					// }
-					// div #awards-section .section {
-					// 	(self.0.awards)
-					// }
-					// div #certificates-section .section {
-					// 	(self.0.certificates)
-					// }
-					// div #volunteer-section .section {
-					// 	(self.0.volunteer)
-					// }
-					// div #interests-section .section {
-					// 	(self.0.interests)
-					// }
-					// div #languages-section .section {
-					// 	(self.0.languages)
-					// }
-					// div #references-section .section {
-					// 	(self.0.references)
-					// }
+					div #work-section .section {
+						(work::Renderer(self.0.work))
+					}
+					div #publications-section .section {
+						(publications::Renderer(self.0.publications, self.1.citation_style))
+					}
+					div #projects-section .section {
+						(projects::Renderer(self.0.projects))
+					}
+					div #skills-section .section {
+						(skills::Renderer(self.0.skills, self.1.skill_level_indicator))
+					}
+					div #awards-section .section {
+						(awards::Renderer(self.0.awards))
+					}
+					div #certificates-section .section {
+						(certificates::Renderer(self.0.certificates))
+					}
+					div #volunteer-section .section {
+						(volunteer::Renderer(self.0.volunteer))
+					}
+					div #interests-section .section {
+						(interests::Renderer(self.0.interests))
+					}
+					div #languages-section .section {
+						(languages::Renderer(self.0.languages))
+					}
+					div #references-section .section {
+						(references::Renderer(self.0.references, self.1.references_on_request))
+					}
 				}
 			}
 		}
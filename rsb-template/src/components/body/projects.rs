@@ -0,0 +1,84 @@
+use hypertext::{html_elements, maud, GlobalAttributes, RenderIterator, Renderable};
+use rsb_schema::ProjectsItem;
+
+use super::common::{DateRangeRenderer, KeywordsRenderer, LinkRenderer};
+use crate::text;
+
+struct ProjectsItemRenderer(ProjectsItem);
+impl Renderable for ProjectsItemRenderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.name.is_none() {
+			log::warn!("No name in ProjectsItem {:?}", self.0);
+		}
+
+		let has_dates = self.0.start_date.is_some() || self.0.end_date.is_some();
+		let subtitle = text::roles(&self.0.roles, self.0.entity);
+
+		maud! {
+			div .item {
+				div .item-header {
+					span .item-header-title {
+						(LinkRenderer(self.0.name, self.0.url))
+					}
+					@if let Some(subtitle) = subtitle {
+						br;
+						span .item-header-subtitle {
+							(subtitle)
+						}
+					}
+				}
+				div .item-details {
+					@if has_dates {
+						span .item-details-title {
+							(DateRangeRenderer(self.0.start_date, self.0.end_date))
+						}
+					}
+					@if let Some(project_type) = self.0.project_type {
+						@if has_dates {
+							br;
+						}
+						span .project-type {
+							(project_type)
+						}
+					}
+				}
+				div .clearfix {}
+				@if let Some(description) = self.0.description {
+					div .item-description {
+						(description)
+					}
+				}
+				@if !self.0.highlights.is_empty() {
+					ul .item-highlights {
+						@for highlight in self.0.highlights {
+							li {
+								(highlight)
+							}
+						}
+					}
+				}
+				(KeywordsRenderer(self.0.keywords))
+			}
+		}
+		.render_to(output);
+	}
+}
+
+pub struct Renderer(pub Vec<ProjectsItem>);
+impl Renderable for Renderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.is_empty() {
+			return;
+		}
+
+		maud! {
+			section #projects {
+				h2 {
+					"Projects"
+				}
+				(self.0.into_iter().map(ProjectsItemRenderer).render_all())
+			}
+		}
+		.render_to(output);
+	}
+}
@@ -0,0 +1,111 @@
+use hypertext::{html_elements, maud, GlobalAttributes, RenderIterator, Renderable};
+use rsb_schema::PublicationsItem;
+
+use super::common::LinkRenderer;
+use crate::{
+	citation::{apa_date, ieee_date, terminated},
+	CitationStyle,
+};
+
+struct ApaRenderer(PublicationsItem);
+impl Renderable for ApaRenderer {
+	fn render_to(self, output: &mut String) {
+		let date = match &self.0.release_date {
+			Some(date) => apa_date(date),
+			None => String::from("n.d."),
+		};
+		let title = self.0.name.as_deref().map(|name| terminated(name, '.'));
+
+		maud! {
+			"(" (date) "). "
+			@if let Some(title) = title {
+				cite {
+					(LinkRenderer(Some(title), self.0.url))
+				}
+			}
+			@if let Some(publisher) = self.0.publisher {
+				" " (terminated(&publisher, '.'))
+			}
+		}
+		.render_to(output);
+	}
+}
+
+struct IeeeRenderer(PublicationsItem);
+impl Renderable for IeeeRenderer {
+	fn render_to(self, output: &mut String) {
+		let has_tail = self.0.publisher.is_some() || self.0.release_date.is_some();
+
+		maud! {
+			@if let Some(title) = self.0.name {
+				"\u{201c}" (LinkRenderer(Some(title), self.0.url)) @if has_tail { "," } "\u{201d} "
+			}
+			@if let Some(publisher) = self.0.publisher {
+				i {
+					(publisher)
+				}
+				@if self.0.release_date.is_some() {
+					", "
+				}
+			}
+			@if let Some(date) = self.0.release_date {
+				(ieee_date(&date))
+			}
+			"."
+		}
+		.render_to(output);
+	}
+}
+
+struct PublicationsItemRenderer {
+	item: PublicationsItem,
+	style: CitationStyle,
+}
+impl Renderable for PublicationsItemRenderer {
+	fn render_to(self, output: &mut String) {
+		if self.item.name.is_none() {
+			log::warn!("No name in PublicationsItem {:?}", self.item);
+		}
+
+		let summary = self.item.summary.clone();
+		maud! {
+			div .item {
+				div .citation {
+					@match self.style {
+						CitationStyle::Apa => (ApaRenderer(self.item)),
+						CitationStyle::Ieee => (IeeeRenderer(self.item)),
+					}
+				}
+				@if let Some(summary) = summary {
+					div .item-summary {
+						(summary)
+					}
+				}
+			}
+		}
+		.render_to(output);
+	}
+}
+
+pub struct Renderer(pub Vec<PublicationsItem>, pub CitationStyle);
+impl Renderable for Renderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.is_empty() {
+			return;
+		}
+
+		let style = self.1;
+		maud! {
+			section #publications {
+				h2 {
+					"Publications"
+				}
+				(self.0
+					.into_iter()
+					.map(|item| PublicationsItemRenderer { item, style })
+					.render_all())
+			}
+		}
+		.render_to(output);
+	}
+}
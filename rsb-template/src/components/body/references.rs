@@ -0,0 +1,55 @@
+use hypertext::{html_elements, maud, GlobalAttributes, RenderIterator, Renderable};
+use rsb_schema::ReferencesItem;
+
+use crate::text::REFERENCES_ON_REQUEST;
+
+struct ReferencesItemRenderer(ReferencesItem);
+impl Renderable for ReferencesItemRenderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.reference.is_none() {
+			log::warn!("No reference in ReferencesItem {:?}", self.0);
+		}
+
+		maud! {
+			div .item {
+				@if let Some(reference) = self.0.reference {
+					blockquote .reference {
+						(reference)
+					}
+				}
+				@if let Some(name) = self.0.name {
+					div .reference-name {
+						"- " (name)
+					}
+				}
+			}
+		}
+		.render_to(output);
+	}
+}
+
+/// Renders references, or only a note that they are available on request when the flag is set
+pub struct Renderer(pub Vec<ReferencesItem>, pub bool);
+impl Renderable for Renderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.is_empty() {
+			return;
+		}
+
+		maud! {
+			section #references {
+				h2 {
+					"References"
+				}
+				@if self.1 {
+					div .item {
+						(REFERENCES_ON_REQUEST)
+					}
+				} @else {
+					(self.0.into_iter().map(ReferencesItemRenderer).render_all())
+				}
+			}
+		}
+		.render_to(output);
+	}
+}
@@ -0,0 +1,88 @@
+use hypertext::{html_elements, maud, GlobalAttributes, RenderIterator, Renderable};
+use rsb_schema::SkillsItem;
+
+use super::common::KeywordsRenderer;
+use crate::levels::{level_score, MAX_LEVEL};
+
+struct LevelIndicatorRenderer(u8);
+impl Renderable for LevelIndicatorRenderer {
+	fn render_to(self, output: &mut String) {
+		maud! {
+			span .level-indicator {
+				@for i in 0..MAX_LEVEL {
+					span .level-dot .filled[i < self.0] {}
+				}
+			}
+		}
+		.render_to(output);
+	}
+}
+
+struct SkillsItemRenderer {
+	item: SkillsItem,
+	show_indicator: bool,
+}
+impl Renderable for SkillsItemRenderer {
+	fn render_to(self, output: &mut String) {
+		if self.item.name.is_none() {
+			log::warn!("No name in SkillsItem {:?}", self.item);
+		}
+
+		let indicator = match (&self.item.level, self.show_indicator) {
+			(Some(level), true) => {
+				let score = level_score(level);
+				if score.is_none() {
+					log::warn!("Could not map level {:?} to an indicator. Showing text only", level);
+				}
+				score
+			}
+			_ => None,
+		};
+
+		maud! {
+			div .item {
+				div .item-header {
+					@if let Some(name) = self.item.name {
+						span .item-header-title .p-skill {
+							(name)
+						}
+					}
+				}
+				div .item-details {
+					@if let Some(level) = self.item.level {
+						span .item-details-title {
+							(level)
+						}
+					}
+					(indicator.map(LevelIndicatorRenderer))
+				}
+				div .clearfix {}
+				(KeywordsRenderer(self.item.keywords))
+			}
+		}
+		.render_to(output);
+	}
+}
+
+pub struct Renderer(pub Vec<SkillsItem>, pub bool);
+impl Renderable for Renderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.is_empty() {
+			return;
+		}
+
+		let show_indicator = self.1;
+		maud! {
+			section #skills {
+				h2 {
+					"Skills"
+				}
+				(self.0
+					.into_iter()
+					.map(|item| SkillsItemRenderer { item, show_indicator })
+					.render_all())
+			}
+		}
+		.render_to(output);
+	}
+}
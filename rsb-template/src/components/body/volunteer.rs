@@ -0,0 +1,73 @@
+use hypertext::{html_elements, maud, GlobalAttributes, RenderIterator, Renderable};
+use rsb_schema::VolunteerItem;
+
+use super::common::{DateRangeRenderer, LinkRenderer};
+
+struct VolunteerItemRenderer(VolunteerItem);
+impl Renderable for VolunteerItemRenderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.organization.is_none() && self.0.position.is_none() {
+			log::warn!("No organization or position in VolunteerItem {:?}", self.0);
+		}
+
+		let has_dates = self.0.start_date.is_some() || self.0.end_date.is_some();
+
+		maud! {
+			div .item {
+				div .item-header {
+					span .item-header-title {
+						(LinkRenderer(self.0.organization, self.0.url))
+					}
+					@if let Some(position) = self.0.position {
+						br;
+						span .item-header-subtitle {
+							(position)
+						}
+					}
+				}
+				div .item-details {
+					@if has_dates {
+						span .item-details-title {
+							(DateRangeRenderer(self.0.start_date, self.0.end_date))
+						}
+					}
+				}
+				div .clearfix {}
+				@if let Some(summary) = self.0.summary {
+					div .item-summary {
+						(summary)
+					}
+				}
+				@if !self.0.highlights.is_empty() {
+					ul .item-highlights {
+						@for highlight in self.0.highlights {
+							li {
+								(highlight)
+							}
+						}
+					}
+				}
+			}
+		}
+		.render_to(output);
+	}
+}
+
+pub struct Renderer(pub Vec<VolunteerItem>);
+impl Renderable for Renderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.is_empty() {
+			return;
+		}
+
+		maud! {
+			section #volunteer {
+				h2 {
+					"Volunteer"
+				}
+				(self.0.into_iter().map(VolunteerItemRenderer).render_all())
+			}
+		}
+		.render_to(output);
+	}
+}
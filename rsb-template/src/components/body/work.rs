@@ -0,0 +1,86 @@
+use hypertext::{html_elements, maud, GlobalAttributes, RenderIterator, Renderable};
+use rsb_schema::WorkItem;
+
+use super::common::{EventDateRangeRenderer, LinkRenderer};
+
+struct WorkItemRenderer(WorkItem);
+impl Renderable for WorkItemRenderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.name.is_none() && self.0.position.is_none() {
+			log::warn!("No name or position in WorkItem {:?}", self.0);
+		}
+
+		let has_dates = self.0.start_date.is_some() || self.0.end_date.is_some();
+
+		maud! {
+			div .item .p-experience .h-event {
+				div .item-header {
+					span .item-header-title .p-name {
+						(LinkRenderer(self.0.name, self.0.url))
+					}
+					@if let Some(position) = self.0.position {
+						br;
+						span .item-header-subtitle {
+							(position)
+						}
+					}
+				}
+				div .item-details {
+					@if has_dates {
+						span .item-details-title {
+							(EventDateRangeRenderer(self.0.start_date, self.0.end_date))
+						}
+					}
+					@if let Some(location) = self.0.location {
+						@if has_dates {
+							br;
+						}
+						span .p-location {
+							(location)
+						}
+					}
+				}
+				div .clearfix {}
+				@if let Some(description) = self.0.description {
+					div .item-description .p-description {
+						(description)
+					}
+				}
+				@if let Some(summary) = self.0.summary {
+					div .item-summary {
+						(summary)
+					}
+				}
+				@if !self.0.highlights.is_empty() {
+					ul .item-highlights {
+						@for highlight in self.0.highlights {
+							li {
+								(highlight)
+							}
+						}
+					}
+				}
+			}
+		}
+		.render_to(output);
+	}
+}
+
+pub struct Renderer(pub Vec<WorkItem>);
+impl Renderable for Renderer {
+	fn render_to(self, output: &mut String) {
+		if self.0.is_empty() {
+			return;
+		}
+
+		maud! {
+			section #work {
+				h2 {
+					"Work"
+				}
+				(self.0.into_iter().map(WorkItemRenderer).render_all())
+			}
+		}
+		.render_to(output);
+	}
+}
@@ -1,24 +1,31 @@
+use chrono::NaiveDate;
 use hypertext::{html_elements, maud, Raw, Renderable};
+
+use super::JsonLd;
+
 const CSS_STR: &str = include_str!("style.css");
 
-pub struct Head(pub Option<String>);
+pub struct Head(pub Option<String>, pub Option<NaiveDate>, pub JsonLd);
 impl Renderable for Head {
 	fn render_to(self, output: &mut String) {
 		let name = self.0.clone().unwrap_or_else(|| String::from("Resume"));
-		let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
 
 		maud! {
 			head {
 				meta charset="utf-8";
 				meta name="viewport" content="width=device-width, user-scalable=no, minimal-ui";
 				title {
-					(name) " - " (date)
+					(name)
+					@if let Some(date) = self.1 {
+						" - " (date.format("%Y-%m-%d").to_string())
+					}
 				}
 				// TODO: unsafe: preescaping is not safe. Validate css before doing this.
 				// Leaving this because this is static css (and ran locally)
 				style {
 					(Raw(CSS_STR))
 				}
+				(self.2)
 			}
 		}
 		.render_to(output);
@@ -0,0 +1,246 @@
+use hypertext::{html_elements, maud, Raw, Renderable};
+use rsb_schema::{Basics, EducationItem, Iso8601, Resume, WorkItem};
+use serde_json::{Map, Value};
+
+use crate::{dates::iso_date, text};
+
+type Object = Map<String, Value>;
+
+fn insert(object: &mut Object, key: &str, value: Option<String>) {
+	if let Some(value) = value.filter(|value| !value.trim().is_empty()) {
+		object.insert(key.into(), Value::String(value));
+	}
+}
+
+fn insert_date(object: &mut Object, key: &str, date: Option<&Iso8601>) {
+	insert(object, key, date.map(iso_date));
+}
+
+fn organization(kind: &str, name: Option<String>, url: Option<String>) -> Value {
+	let mut object = Object::new();
+	object.insert("@type".into(), kind.into());
+	insert(&mut object, "name", name);
+	insert(&mut object, "url", url);
+	Value::Object(object)
+}
+
+/// Schema.org role pattern: `property` holds a role that wraps the organization under the same property name
+fn role(
+	kind: &str,
+	property: &str,
+	organization: Value,
+	name: Option<String>,
+	start: Option<&Iso8601>,
+	end: Option<&Iso8601>,
+) -> Value {
+	let mut object = Object::new();
+	object.insert("@type".into(), kind.into());
+	object.insert(property.into(), organization);
+	insert(&mut object, "roleName", name);
+	insert_date(&mut object, "startDate", start);
+	insert_date(&mut object, "endDate", end);
+	Value::Object(object)
+}
+
+fn address(basics: &Basics) -> Option<Value> {
+	let location = basics.location.clone();
+	let mut object = Object::new();
+	insert(&mut object, "streetAddress", location.address);
+	insert(&mut object, "addressLocality", location.city);
+	insert(&mut object, "addressRegion", location.region);
+	insert(&mut object, "postalCode", location.postal_code);
+	insert(&mut object, "addressCountry", location.country_code);
+
+	match object.is_empty() {
+		true => None,
+		false => {
+			object.insert("@type".into(), "PostalAddress".into());
+			Some(Value::Object(object))
+		}
+	}
+}
+
+fn work_role(item: &WorkItem) -> (bool, Value) {
+	let current = item.end_date.is_none();
+	let organization = organization("Organization", item.name.clone(), item.url.clone());
+	let property = if current { "worksFor" } else { "alumniOf" };
+	let kind = if current { "EmployeeRole" } else { "OrganizationRole" };
+	let role = role(
+		kind,
+		property,
+		organization,
+		item.position.clone(),
+		item.start_date.as_ref(),
+		item.end_date.as_ref(),
+	);
+	(current, role)
+}
+
+fn education_role(item: &EducationItem) -> Value {
+	let degree = text::degree(item.study_type.clone(), item.area.clone());
+	let organization = organization("EducationalOrganization", item.institution.clone(), item.url.clone());
+	role(
+		"OrganizationRole",
+		"alumniOf",
+		organization,
+		degree,
+		item.start_date.as_ref(),
+		item.end_date.as_ref(),
+	)
+}
+
+/// Schema.org `Person` built from basics, work and education. Jobs without an end date are current employers,
+/// the rest become `alumniOf` alongside schools
+pub struct JsonLd(pub Value);
+impl From<&Resume> for JsonLd {
+	fn from(val: &Resume) -> Self {
+		let basics = &val.basics;
+		let mut person = Object::new();
+		person.insert("@context".into(), "https://schema.org".into());
+		person.insert("@type".into(), "Person".into());
+		insert(&mut person, "name", basics.name.clone());
+		insert(&mut person, "jobTitle", basics.label.clone());
+		insert(
+			&mut person,
+			"email",
+			basics.email.as_ref().map(|email| format!("mailto:{}", email)),
+		);
+		insert(&mut person, "telephone", basics.phone.clone());
+		insert(&mut person, "url", basics.url.clone());
+		insert(&mut person, "image", basics.image.clone());
+		insert(&mut person, "description", basics.summary.clone());
+		if let Some(address) = address(basics) {
+			person.insert("address".into(), address);
+		}
+
+		let same_as: Vec<Value> = basics
+			.profiles
+			.iter()
+			.filter_map(|profile| profile.url.clone())
+			.map(Value::String)
+			.collect();
+		if !same_as.is_empty() {
+			person.insert("sameAs".into(), Value::Array(same_as));
+		}
+
+		let mut works_for = Vec::new();
+		let mut alumni_of = Vec::new();
+		for item in &val.work {
+			match work_role(item) {
+				(true, role) => works_for.push(role),
+				(false, role) => alumni_of.push(role),
+			}
+		}
+		alumni_of.extend(val.education.iter().map(education_role));
+
+		if !works_for.is_empty() {
+			person.insert("worksFor".into(), Value::Array(works_for));
+		}
+		if !alumni_of.is_empty() {
+			person.insert("alumniOf".into(), Value::Array(alumni_of));
+		}
+
+		JsonLd(Value::Object(person))
+	}
+}
+
+impl Renderable for JsonLd {
+	fn render_to(self, output: &mut String) {
+		// `<` can not appear escaped inside a script element, so keep `</script>` out of the JSON itself
+		let json = self.0.to_string().replace('<', "\\u003c");
+
+		maud! {
+			script type="application/ld+json" {
+				(Raw(json))
+			}
+		}
+		.render_to(output);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::*;
+
+	fn person(json: &str) -> Value {
+		let val: Resume = serde_json::from_str(json).unwrap();
+		JsonLd::from(&val).0
+	}
+
+	#[test]
+	fn current_jobs_are_employers_and_past_ones_alumni() {
+		let person = person(
+			r#"{"work": [
+				{"name": "Now", "position": "Lead", "startDate": "2022-01"},
+				{"name": "Before", "startDate": "2019", "endDate": "2021-12-31"}
+			]}"#,
+		);
+		assert_eq!(
+			person["worksFor"],
+			json!([{
+				"@type": "EmployeeRole",
+				"worksFor": {"@type": "Organization", "name": "Now"},
+				"roleName": "Lead",
+				"startDate": "2022-01"
+			}])
+		);
+		assert_eq!(
+			person["alumniOf"],
+			json!([{
+				"@type": "OrganizationRole",
+				"alumniOf": {"@type": "Organization", "name": "Before"},
+				"startDate": "2019",
+				"endDate": "2021-12-31"
+			}])
+		);
+	}
+
+	#[test]
+	fn education_is_added_to_alumni() {
+		let person = person(
+			r#"{
+				"work": [{"name": "Before", "endDate": "2020"}],
+				"education": [{"institution": "MIT", "url": "https://mit.edu", "studyType": "BSc", "area": "Physics"}]
+			}"#,
+		);
+		let alumni = person["alumniOf"].as_array().unwrap();
+		assert_eq!(alumni.len(), 2);
+		assert_eq!(
+			alumni[1],
+			json!({
+				"@type": "OrganizationRole",
+				"alumniOf": {"@type": "EducationalOrganization", "name": "MIT", "url": "https://mit.edu"},
+				"roleName": "BSc in Physics"
+			})
+		);
+		assert!(person.get("worksFor").is_none());
+	}
+
+	#[test]
+	fn empty_fields_are_left_out() {
+		let empty =
+			person(r#"{"basics": {"name": "Jane", "location": {"city": " "}, "profiles": [{"network": "X"}]}}"#);
+		assert_eq!(
+			empty,
+			json!({"@context": "https://schema.org", "@type": "Person", "name": "Jane"})
+		);
+
+		let filled =
+			person(r#"{"basics": {"location": {"city": "Berlin"}, "profiles": [{"url": "https://github.com/jane"}]}}"#);
+		assert_eq!(
+			filled["address"],
+			json!({"@type": "PostalAddress", "addressLocality": "Berlin"})
+		);
+		assert_eq!(filled["sameAs"], json!(["https://github.com/jane"]));
+	}
+
+	#[test]
+	fn script_end_tag_is_escaped() {
+		let mut html = String::new();
+		JsonLd(json!({"name": "</script><b>"})).render_to(&mut html);
+		assert!(html.contains(r#"{"name":"\u003c/script>\u003cb>"}"#));
+		assert_eq!(html.matches("</script>").count(), 1);
+	}
+}
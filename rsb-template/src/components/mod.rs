@@ -1,5 +1,7 @@
 mod body;
 mod head;
+mod json_ld;
 
 pub use body::Body;
 pub use head::Head;
+pub use json_ld::JsonLd;
@@ -0,0 +1,100 @@
+use chrono::{DateTime, NaiveDate};
+use rsb_schema::Iso8601;
+
+const MONTHS: [&str; 12] = [
+	"Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+pub const PRESENT: &str = "Present";
+const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
+pub fn month_name(month: u8) -> &'static str {
+	MONTHS.get(month.saturating_sub(1) as usize).copied().unwrap_or("???")
+}
+
+pub fn format_date(date: &Iso8601) -> String {
+	match *date {
+		Iso8601::Year(y) => format!("{}", y),
+		Iso8601::YearMonth(y, m) => format!("{} {}", month_name(m), y),
+		Iso8601::Full(y, m, d) => format!("{} {}, {}", month_name(m), d, y),
+	}
+}
+
+/// Zero-padded ISO 8601 form of `date`, for machine-readable output
+pub fn iso_date(date: &Iso8601) -> String {
+	match *date {
+		Iso8601::Year(y) => format!("{:04}", y),
+		Iso8601::YearMonth(y, m) => format!("{:04}-{:02}", y, m),
+		Iso8601::Full(y, m, d) => format!("{:04}-{:02}-{:02}", y, m, d),
+	}
+}
+
+/// Formats `start - end`, with a missing end date shown as an open range
+pub fn format_range(start: Option<&Iso8601>, end: Option<&Iso8601>) -> Option<String> {
+	match (start, end) {
+		(Some(start), Some(end)) => Some(format!("{} - {}", format_date(start), format_date(end))),
+		(Some(start), None) => Some(format!("{} - {}", format_date(start), PRESENT)),
+		(None, Some(end)) => Some(format_date(end)),
+		(None, None) => None,
+	}
+}
+
+/// Date a document is stamped with: `explicit` if given, else `SOURCE_DATE_EPOCH`, else `meta.lastModified`.
+/// Never the wall clock, so the same input always renders the same bytes
+pub fn document_date(explicit: Option<NaiveDate>, last_modified: Option<&str>) -> Option<NaiveDate> {
+	if explicit.is_some() {
+		return explicit;
+	}
+
+	if let Ok(epoch) = std::env::var(SOURCE_DATE_EPOCH) {
+		match epoch
+			.trim()
+			.parse()
+			.ok()
+			.and_then(|secs| DateTime::from_timestamp(secs, 0))
+		{
+			Some(date) => return Some(date.date_naive()),
+			None => log::warn!("ignoring invalid {}={:?}", SOURCE_DATE_EPOCH, epoch),
+		}
+	}
+
+	// YYYY-MM-DDThh:mm:ss, only the date part is needed
+	let last_modified = last_modified?;
+	match last_modified.get(..10).and_then(|date| date.parse().ok()) {
+		Some(date) => Some(date),
+		None => {
+			log::warn!("ignoring invalid meta.lastModified {:?}", last_modified);
+			None
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn iso_date_zero_pads() {
+		assert_eq!(iso_date(&Iso8601::Year(800)), "0800");
+		assert_eq!(iso_date(&Iso8601::YearMonth(2020, 3)), "2020-03");
+		assert_eq!(iso_date(&Iso8601::Full(2020, 3, 4)), "2020-03-04");
+	}
+
+	#[test]
+	fn document_date_precedence() {
+		let explicit = NaiveDate::from_ymd_opt(2021, 5, 6);
+		let modified = Some("2023-01-02T10:00:00");
+
+		// the only test touching the variable, so nothing else sees it set
+		std::env::set_var(SOURCE_DATE_EPOCH, "1700000000");
+		assert_eq!(document_date(explicit, modified), explicit);
+		assert_eq!(document_date(None, modified), NaiveDate::from_ymd_opt(2023, 11, 14));
+
+		std::env::set_var(SOURCE_DATE_EPOCH, "not a number");
+		assert_eq!(document_date(None, modified), NaiveDate::from_ymd_opt(2023, 1, 2));
+
+		std::env::remove_var(SOURCE_DATE_EPOCH);
+		assert_eq!(document_date(None, modified), NaiveDate::from_ymd_opt(2023, 1, 2));
+		assert_eq!(document_date(None, Some("yesterday")), None);
+		assert_eq!(document_date(None, None), None);
+	}
+}
@@ -0,0 +1,324 @@
+use std::{
+	fmt::Write as _,
+	io::{Cursor, Write as _},
+};
+
+use rsb_schema::{Basics, Resume};
+use zip::{write::SimpleFileOptions, CompressionMethod, DateTime, ZipWriter};
+
+use crate::{
+	outline::{Entry, Header, Link, Outline, Section},
+	xml::{dublin_core, escape},
+	Options,
+};
+
+// relationship ids of document.xml, hyperlinks are numbered after these
+const STYLES_ID: &str = "rId1";
+const NUMBERING_ID: &str = "rId2";
+const FIRST_LINK_ID: usize = 3;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+<Default Extension="xml" ContentType="application/xml"/>
+<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+<Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/>
+<Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/>
+<Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>
+<Override PartName="/docProps/app.xml" ContentType="application/vnd.openxmlformats-officedocument.extended-properties+xml"/>
+</Types>
+"#;
+
+const PACKAGE_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>
+<Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties" Target="docProps/app.xml"/>
+</Relationships>
+"#;
+
+const APP: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties">
+<Application>rsb</Application>
+</Properties>
+"#;
+
+/// A4 with 18mm margins, in twentieths of a point. Entry dates are right aligned on a tab stop at the right margin
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:docDefaults>
+<w:rPrDefault><w:rPr><w:rFonts w:ascii="Georgia" w:hAnsi="Georgia" w:cs="Georgia"/><w:sz w:val="20"/><w:szCs w:val="20"/></w:rPr></w:rPrDefault>
+<w:pPrDefault><w:pPr><w:spacing w:after="60" w:line="259" w:lineRule="auto"/></w:pPr></w:pPrDefault>
+</w:docDefaults>
+<w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style>
+<w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/>
+<w:pPr><w:jc w:val="center"/><w:spacing w:after="0"/></w:pPr><w:rPr><w:b/><w:sz w:val="40"/><w:szCs w:val="40"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Subtitle"><w:name w:val="Subtitle"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/>
+<w:pPr><w:jc w:val="center"/></w:pPr><w:rPr><w:i/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Contacts"><w:name w:val="Contacts"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/>
+<w:pPr><w:jc w:val="center"/><w:spacing w:after="160"/></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/>
+<w:pPr><w:keepNext/><w:spacing w:before="240" w:after="80"/><w:pBdr><w:bottom w:val="single" w:sz="4" w:space="1" w:color="auto"/></w:pBdr><w:outlineLvl w:val="0"/></w:pPr>
+<w:rPr><w:b/><w:sz w:val="24"/><w:szCs w:val="24"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/>
+<w:pPr><w:keepNext/><w:tabs><w:tab w:val="right" w:pos="9866"/></w:tabs><w:spacing w:before="120" w:after="0"/><w:outlineLvl w:val="1"/></w:pPr>
+<w:rPr><w:b/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="EntryDetails"><w:name w:val="Entry Details"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/>
+<w:pPr><w:keepNext/><w:tabs><w:tab w:val="right" w:pos="9866"/></w:tabs></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="ListBullet"><w:name w:val="List Bullet"/><w:basedOn w:val="Normal"/>
+<w:pPr><w:numPr><w:numId w:val="1"/></w:numPr><w:spacing w:after="0"/></w:pPr></w:style>
+<w:style w:type="character" w:styleId="Hyperlink"><w:name w:val="Hyperlink"/><w:rPr><w:color w:val="0563C1"/><w:u w:val="single"/></w:rPr></w:style>
+</w:styles>
+"#;
+
+const NUMBERING: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:abstractNum w:abstractNumId="0"><w:multiLevelType w:val="singleLevel"/>
+<w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="bullet"/><w:lvlText w:val="•"/><w:lvlJc w:val="left"/>
+<w:pPr><w:ind w:left="360" w:hanging="360"/></w:pPr></w:lvl>
+</w:abstractNum>
+<w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num>
+</w:numbering>
+"#;
+
+/// `word/document.xml` under construction, along with the hyperlink targets it references
+#[derive(Default)]
+struct Document {
+	body: String,
+	links: Vec<String>,
+}
+
+/// A run of plain text. Line breaks in `text` become `<w:br/>`
+fn run(text: &str, props: &str) -> String {
+	let mut res = String::from("<w:r>");
+	if !props.is_empty() {
+		res.push_str(&format!("<w:rPr>{}</w:rPr>", props));
+	}
+	for (i, line) in text.lines().enumerate() {
+		if i > 0 {
+			res.push_str("<w:br/>");
+		}
+		res.push_str(&format!(r#"<w:t xml:space="preserve">{}</w:t>"#, escape(line)));
+	}
+	res.push_str("</w:r>");
+	res
+}
+
+impl Document {
+	/// A run for `link`, inside a hyperlink when it has a target. `props` are added to the run properties
+	fn link(&mut self, link: &Link, props: &str) -> String {
+		match &link.url {
+			Some(url) => {
+				self.links.push(url.clone());
+				let id = FIRST_LINK_ID + self.links.len() - 1;
+				format!(
+					r#"<w:hyperlink r:id="rId{}">{}</w:hyperlink>"#,
+					id,
+					run(&link.text, &format!(r#"<w:rStyle w:val="Hyperlink"/>{}"#, props))
+				)
+			}
+			None => run(&link.text, props),
+		}
+	}
+
+	fn paragraph(&mut self, style: Option<&str>, runs: &str) {
+		self.body.push_str("<w:p>");
+		if let Some(style) = style {
+			self.body
+				.push_str(&format!(r#"<w:pPr><w:pStyle w:val="{}"/></w:pPr>"#, style));
+		}
+		self.body.push_str(runs);
+		self.body.push_str("</w:p>\n");
+	}
+
+	fn header(&mut self, header: Header) {
+		if let Some(name) = &header.name {
+			self.paragraph(Some("Title"), &run(name, ""));
+		}
+		if let Some(label) = &header.label {
+			self.paragraph(Some("Subtitle"), &run(label, ""));
+		}
+
+		let mut contacts: Vec<String> = header.contacts.iter().map(|contact| self.link(contact, "")).collect();
+		contacts.extend(header.location.as_deref().map(|location| run(location, "")));
+		if !contacts.is_empty() {
+			self.paragraph(Some("Contacts"), &contacts.join(&run(" | ", "")));
+		}
+
+		if let Some(summary) = &header.summary {
+			self.paragraph(None, &run(summary, ""));
+		}
+	}
+
+	fn entry(&mut self, entry: Entry) {
+		if entry.title.is_some() || entry.date.is_some() {
+			let mut runs = entry
+				.title
+				.as_ref()
+				.map(|title| self.link(title, ""))
+				.unwrap_or_default();
+			if let Some(date) = &entry.date {
+				runs.push_str("<w:r><w:tab/></w:r>");
+				runs.push_str(&run(date, ""));
+			}
+			self.paragraph(Some("Heading2"), &runs);
+		}
+
+		if entry.subtitle.is_some() || entry.location.is_some() {
+			let mut runs = entry
+				.subtitle
+				.as_ref()
+				.map(|subtitle| self.link(subtitle, "<w:i/>"))
+				.unwrap_or_default();
+			if let Some(location) = &entry.location {
+				runs.push_str("<w:r><w:tab/></w:r>");
+				runs.push_str(&run(location, ""));
+			}
+			self.paragraph(Some("EntryDetails"), &runs);
+		}
+
+		for paragraph in &entry.paragraphs {
+			self.paragraph(None, &run(paragraph, ""));
+		}
+		for highlight in &entry.highlights {
+			self.paragraph(Some("ListBullet"), &run(highlight, ""));
+		}
+		if !entry.keywords.is_empty() {
+			let runs = run("Keywords: ", "<w:i/>") + &run(&entry.keywords.join(", "), "");
+			self.paragraph(None, &runs);
+		}
+	}
+
+	fn section(&mut self, section: Section) {
+		self.paragraph(Some("Heading1"), &run(section.kind.title(), ""));
+		for item in section.entries {
+			self.entry(item);
+		}
+	}
+
+	fn document_xml(&self) -> String {
+		format!(
+			concat!(
+				r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+				"\n",
+				r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" "#,
+				r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+				"\n<w:body>\n{}",
+				r#"<w:sectPr><w:pgSz w:w="11906" w:h="16838"/>"#,
+				r#"<w:pgMar w:top="1020" w:right="1020" w:bottom="1020" w:left="1020" w:header="0" w:footer="0" w:gutter="0"/>"#,
+				"</w:sectPr>\n</w:body>\n</w:document>\n"
+			),
+			self.body
+		)
+	}
+
+	fn rels_xml(&self) -> Result<String, std::fmt::Error> {
+		let mut res = String::from(concat!(
+			r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+			"\n",
+			r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+			"\n"
+		));
+		writeln!(
+			res,
+			r#"<Relationship Id="{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>"#,
+			STYLES_ID
+		)?;
+		writeln!(
+			res,
+			r#"<Relationship Id="{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/>"#,
+			NUMBERING_ID
+		)?;
+		for (i, url) in self.links.iter().enumerate() {
+			writeln!(
+				res,
+				r#"<Relationship Id="rId{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="{}" TargetMode="External"/>"#,
+				FIRST_LINK_ID + i,
+				escape(url)
+			)?;
+		}
+		res.push_str("</Relationships>\n");
+		Ok(res)
+	}
+}
+
+fn core_xml(basics: &Basics) -> String {
+	let mut res = String::from(concat!(
+		r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+		"\n",
+		r#"<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" "#,
+		r#"xmlns:dc="http://purl.org/dc/elements/1.1/">"#,
+		"\n"
+	));
+	res.push_str(&dublin_core(basics));
+	res.push_str("</cp:coreProperties>\n");
+	res
+}
+
+/// Renders `val` into an Office Open XML (`.docx`) package
+pub fn generate(val: Resume, opts: Options) -> anyhow::Result<Vec<u8>> {
+	let core = core_xml(&val.basics);
+	let outline = Outline::new(val, &opts);
+
+	let mut doc = Document::default();
+	doc.header(outline.header);
+	for section in outline.sections {
+		doc.section(section);
+	}
+
+	let parts = [
+		("[Content_Types].xml", String::from(CONTENT_TYPES)),
+		("_rels/.rels", String::from(PACKAGE_RELS)),
+		("docProps/core.xml", core),
+		("docProps/app.xml", String::from(APP)),
+		("word/document.xml", doc.document_xml()),
+		("word/_rels/document.xml.rels", doc.rels_xml()?),
+		("word/styles.xml", String::from(STYLES)),
+		("word/numbering.xml", String::from(NUMBERING)),
+	];
+
+	let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+	// a fixed timestamp, as zip falls back to the wall clock when its time feature is on
+	let options = SimpleFileOptions::default()
+		.compression_method(CompressionMethod::Deflated)
+		.last_modified_time(DateTime::default());
+	for (name, content) in parts {
+		zip.start_file(name, options)?;
+		zip.write_all(content.as_bytes())?;
+	}
+	Ok(zip.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Read;
+
+	use rsb_schema::EducationItem;
+	use zip::ZipArchive;
+
+	use super::*;
+
+	#[test]
+	fn education_url_stays_a_link_as_subtitle() {
+		let val = Resume {
+			education: vec![EducationItem {
+				institution: Some(String::from("MIT")),
+				url: Some(String::from("https://mit.edu")),
+				study_type: Some(String::from("BSc")),
+				area: Some(String::from("Physics")),
+				..Default::default()
+			}],
+			..Default::default()
+		};
+		let docx = generate(val, Options::default()).unwrap();
+
+		let mut archive = ZipArchive::new(Cursor::new(docx)).unwrap();
+		let mut rels = String::new();
+		archive
+			.by_name("word/_rels/document.xml.rels")
+			.unwrap()
+			.read_to_string(&mut rels)
+			.unwrap();
+		assert!(rels.contains(r#"Target="https://mit.edu""#));
+	}
+}
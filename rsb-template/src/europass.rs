@@ -0,0 +1,366 @@
+use std::collections::BTreeSet;
+
+use rsb_schema::{EducationItem, Iso8601, LanguagesItem, Resume, WorkItem};
+
+use crate::{vcard::split_name, xml::escape};
+
+const HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<SkillsPassport xmlns="http://europass.cedefop.europa.eu/Europass" locale="en">
+<DocumentInfo>
+<DocumentType>ECV</DocumentType>
+<Generator>rsb</Generator>
+<XSDVersion>V3.4</XSDVersion>
+</DocumentInfo>
+"#;
+
+const FOOTER: &str = "</SkillsPassport>\n";
+
+/// Element names of the CEFR self-assessment grid, with how they are written in `LanguagesItem.fluency`
+pub const CEFR_SKILLS: [(&str, &str); 5] = [
+	("Listening", "Listening"),
+	("Reading", "Reading"),
+	("SpokenInteraction", "Spoken interaction"),
+	("SpokenProduction", "Spoken production"),
+	("Writing", "Writing"),
+];
+
+const LEVELS: [&str; 6] = ["A1", "A2", "B1", "B2", "C1", "C2"];
+
+/// Fields of the resume that have nowhere to go, reported once the whole resume is written
+#[derive(Default)]
+struct Unmapped(BTreeSet<&'static str>);
+impl Unmapped {
+	fn check(&mut self, field: &'static str, present: bool) {
+		if present {
+			self.0.insert(field);
+		}
+	}
+}
+
+fn present(value: &Option<String>) -> bool {
+	value.as_deref().is_some_and(|value| !value.trim().is_empty())
+}
+
+/// `<name>text</name>`, or nothing for a missing or blank value
+fn leaf(name: &str, text: Option<&str>) -> String {
+	match text.map(str::trim).filter(|text| !text.is_empty()) {
+		Some(text) => format!("<{name}>{}</{name}>", escape(text)),
+		None => String::new(),
+	}
+}
+
+/// `<name>` around the children on their own lines, or nothing when every child is empty
+fn node(name: &str, children: &[String]) -> String {
+	let children: Vec<&str> = children
+		.iter()
+		.map(String::as_str)
+		.filter(|child| !child.is_empty())
+		.collect();
+	match children.is_empty() {
+		true => String::new(),
+		false => format!("<{name}>\n{}\n</{name}>", children.join("\n")),
+	}
+}
+
+/// Dates use the XML Schema partial date types, e.g. `month="--01"`
+fn date(name: &str, date: Option<&Iso8601>) -> String {
+	match date {
+		Some(Iso8601::Year(y)) => format!(r#"<{} year="{}"/>"#, name, y),
+		Some(Iso8601::YearMonth(y, m)) => format!(r#"<{} year="{}" month="--{:02}"/>"#, name, y, m),
+		Some(Iso8601::Full(y, m, d)) => format!(r#"<{} year="{}" month="--{:02}" day="---{:02}"/>"#, name, y, m, d),
+		None => String::new(),
+	}
+}
+
+fn period(start: Option<&Iso8601>, end: Option<&Iso8601>) -> String {
+	let current = match (start, end) {
+		(Some(_), None) => leaf("Current", Some("true")),
+		_ => String::new(),
+	};
+	node("Period", &[date("From", start), date("To", end), current])
+}
+
+/// Europass keeps descriptions as HTML, escaped once more inside the XML
+fn rich_text(text: Option<&str>, items: &[String]) -> Option<String> {
+	let mut html: String = text
+		.unwrap_or_default()
+		.split("\n\n")
+		.map(str::trim)
+		.filter(|paragraph| !paragraph.is_empty())
+		.map(|paragraph| format!("<p>{}</p>", escape(paragraph).replace('\n', "<br/>")))
+		.collect();
+	if !items.is_empty() {
+		html.push_str("<ul>");
+		for item in items {
+			html.push_str(&format!("<li>{}</li>", escape(item)));
+		}
+		html.push_str("</ul>");
+	}
+	Some(html).filter(|html| !html.is_empty())
+}
+
+/// Europass wants first and family names apart, split the same way as in the vCard
+fn person_name(name: Option<&str>) -> String {
+	let (first, surname) = split_name(name.unwrap_or_default());
+	node(
+		"PersonName",
+		&[leaf("FirstName", Some(first)), leaf("Surname", Some(surname))],
+	)
+}
+
+fn identification(val: &Resume, unmapped: &mut Unmapped) -> String {
+	let basics = &val.basics;
+	let location = &basics.location;
+	unmapped.check("basics.location.region", present(&location.region));
+	unmapped.check("basics.image", present(&basics.image));
+
+	let country = node("Country", &[leaf("Code", location.country_code.as_deref())]);
+	let address = node(
+		"Contact",
+		&[
+			leaf("AddressLine", location.address.as_deref()),
+			leaf("PostalCode", location.postal_code.as_deref()),
+			leaf("Municipality", location.city.as_deref()),
+			country,
+		],
+	);
+
+	let mut websites = vec![basics.url.clone()];
+	for profile in &basics.profiles {
+		// only the url has a place, as a website
+		unmapped.check("basics.profiles.network", present(&profile.network));
+		unmapped.check("basics.profiles.username", present(&profile.username));
+		websites.push(profile.url.clone());
+	}
+	let websites: Vec<String> = websites
+		.iter()
+		.map(|url| node("Website", &[leaf("Contact", url.as_deref())]))
+		.collect();
+
+	let contact_info = node(
+		"ContactInfo",
+		&[
+			node("Address", &[address]),
+			node("Email", &[leaf("Contact", basics.email.as_deref())]),
+			node(
+				"TelephoneList",
+				&[node("Telephone", &[leaf("Contact", basics.phone.as_deref())])],
+			),
+			node("WebsiteList", &websites),
+		],
+	);
+
+	node("Identification", &[person_name(basics.name.as_deref()), contact_info])
+}
+
+/// Only one headline fits. The label wins, the summary is used as the personal statement when there is none
+fn headline(val: &Resume, unmapped: &mut Unmapped) -> String {
+	let basics = &val.basics;
+	let (code, text) = match (present(&basics.label), present(&basics.summary)) {
+		(true, summary) => {
+			unmapped.check("basics.summary", summary);
+			("position", basics.label.as_deref())
+		}
+		(false, _) => ("personal_statement", basics.summary.as_deref()),
+	};
+	match text {
+		Some(_) => node(
+			"Headline",
+			&[
+				node("Type", &[leaf("Code", Some(code))]),
+				node("Description", &[leaf("Label", text)]),
+			],
+		),
+		None => String::new(),
+	}
+}
+
+fn work_experience(item: &WorkItem, unmapped: &mut Unmapped) -> String {
+	unmapped.check("work.description", present(&item.description));
+
+	let address = node(
+		"Address",
+		&[node("Contact", &[leaf("Municipality", item.location.as_deref())])],
+	);
+	let website = node("Website", &[leaf("Contact", item.url.as_deref())]);
+	node(
+		"WorkExperience",
+		&[
+			period(item.start_date.as_ref(), item.end_date.as_ref()),
+			node("Position", &[leaf("Label", item.position.as_deref())]),
+			leaf(
+				"Activities",
+				rich_text(item.summary.as_deref(), &item.highlights).as_deref(),
+			),
+			node(
+				"Employer",
+				&[
+					leaf("Name", item.name.as_deref()),
+					node("ContactInfo", &[address, website]),
+				],
+			),
+		],
+	)
+}
+
+fn education(item: &EducationItem, unmapped: &mut Unmapped) -> String {
+	unmapped.check("education.score", present(&item.score));
+
+	let website = node("Website", &[leaf("Contact", item.url.as_deref())]);
+	node(
+		"Education",
+		&[
+			period(item.start_date.as_ref(), item.end_date.as_ref()),
+			leaf("Title", item.study_type.as_deref()),
+			leaf("Activities", rich_text(None, &item.courses).as_deref()),
+			node(
+				"Organisation",
+				&[
+					leaf("Name", item.institution.as_deref()),
+					node("ContactInfo", &[website]),
+				],
+			),
+			node("Field", &[leaf("Label", item.area.as_deref())]),
+		],
+	)
+}
+
+enum Fluency {
+	Native,
+	Levels([Option<&'static str>; 5]),
+	Unknown,
+}
+
+/// Reads `C1` for all skills, or the per skill form `Listening C1, Reading C2, ...` that the importer writes
+fn fluency(value: &str) -> Fluency {
+	let lower = value.to_lowercase();
+	if lower.contains("native") || lower.contains("mother tongue") {
+		return Fluency::Native;
+	}
+
+	let mut levels = [None; 5];
+	for part in value.split(',') {
+		let part = part.trim();
+		let (skill, level) = part.rsplit_once(' ').unwrap_or(("", part));
+		let Some(level) = LEVELS.iter().find(|known| known.eq_ignore_ascii_case(level)) else {
+			continue;
+		};
+		let skill = skill.trim();
+		match CEFR_SKILLS
+			.iter()
+			.position(|(_, label)| label.eq_ignore_ascii_case(skill))
+		{
+			Some(i) => levels[i] = Some(*level),
+			None if skill.is_empty() => levels = [Some(*level); 5],
+			None => {}
+		}
+	}
+
+	match levels.iter().any(Option::is_some) {
+		true => Fluency::Levels(levels),
+		false => Fluency::Unknown,
+	}
+}
+
+fn linguistic(languages: &[LanguagesItem], unmapped: &mut Unmapped) -> String {
+	let mut mother_tongues = Vec::new();
+	let mut foreign = Vec::new();
+	for item in languages {
+		let description = node("Description", &[leaf("Label", item.language.as_deref())]);
+		match item.fluency.as_deref().map(fluency) {
+			Some(Fluency::Native) => mother_tongues.push(node("MotherTongue", &[description])),
+			Some(Fluency::Levels(levels)) => {
+				let levels: Vec<String> = CEFR_SKILLS
+					.iter()
+					.zip(levels)
+					.map(|((name, _), level)| leaf(name, level))
+					.collect();
+				foreign.push(node(
+					"ForeignLanguage",
+					&[description, node("ProficiencyLevel", &levels)],
+				));
+			}
+			Some(Fluency::Unknown) | None => {
+				unmapped.check("languages.fluency", present(&item.fluency));
+				foreign.push(node("ForeignLanguage", &[description]));
+			}
+		}
+	}
+
+	node(
+		"Linguistic",
+		&[
+			node("MotherTongueList", &mother_tongues),
+			node("ForeignLanguageList", &foreign),
+		],
+	)
+}
+
+/// Renders the resume as a Europass CV. Fields Europass has no place for are logged as warnings
+pub fn generate(val: Resume) -> anyhow::Result<String> {
+	let mut unmapped = Unmapped::default();
+
+	let identification = identification(&val, &mut unmapped);
+	let headline = headline(&val, &mut unmapped);
+	let work: Vec<String> = val
+		.work
+		.iter()
+		.map(|item| work_experience(item, &mut unmapped))
+		.collect();
+	let education: Vec<String> = val
+		.education
+		.iter()
+		.map(|item| education(item, &mut unmapped))
+		.collect();
+	let skills = node("Skills", &[linguistic(&val.languages, &mut unmapped)]);
+
+	unmapped.check("awards", !val.awards.is_empty());
+	unmapped.check("certificates", !val.certificates.is_empty());
+	unmapped.check("interests", !val.interests.is_empty());
+	unmapped.check("projects", !val.projects.is_empty());
+	unmapped.check("publications", !val.publications.is_empty());
+	unmapped.check("references", !val.references.is_empty());
+	unmapped.check("skills", !val.skills.is_empty());
+	unmapped.check("volunteer", !val.volunteer.is_empty());
+	if !unmapped.0.is_empty() {
+		let fields: Vec<&str> = unmapped.0.into_iter().collect();
+		log::warn!("could not map to Europass: {}", fields.join(", "));
+	}
+
+	let learner_info = node(
+		"LearnerInfo",
+		&[
+			identification,
+			headline,
+			node("WorkExperienceList", &work),
+			node("EducationList", &education),
+			skills,
+		],
+	);
+	Ok(format!("{}{}\n{}", HEADER, learner_info, FOOTER))
+}
+
+#[cfg(test)]
+mod tests {
+	use rsb_schema::BasicsProfilesItem;
+
+	use super::*;
+
+	#[test]
+	fn profile_names_are_reported_even_with_a_url() {
+		let mut val = Resume::default();
+		val.basics.profiles.push(BasicsProfilesItem {
+			network: Some(String::from("GitHub")),
+			username: Some(String::from("jdoe")),
+			url: Some(String::from("https://github.com/jdoe")),
+		});
+
+		let mut unmapped = Unmapped::default();
+		let xml = identification(&val, &mut unmapped);
+		assert!(xml.contains("<Contact>https://github.com/jdoe</Contact>"));
+		assert_eq!(
+			unmapped.0.into_iter().collect::<Vec<_>>(),
+			["basics.profiles.network", "basics.profiles.username"]
+		);
+	}
+}
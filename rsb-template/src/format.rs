@@ -0,0 +1,53 @@
+use std::path::Path;
+
+use anyhow::{anyhow, bail};
+
+/// Output formats a resume can be rendered into
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Format {
+	#[default]
+	Html,
+	Pdf,
+	#[cfg_attr(feature = "clap", value(alias = "md"))]
+	Markdown,
+	/// plain text suited for applicant tracking systems
+	#[cfg_attr(feature = "clap", value(alias = "txt"))]
+	Text,
+	#[cfg_attr(feature = "clap", value(alias = "tex"))]
+	Latex,
+	#[cfg_attr(feature = "clap", value(alias = "typ"))]
+	Typst,
+	/// Office Open XML, as used by Microsoft Word
+	Docx,
+	/// OpenDocument text
+	Odt,
+	/// vCard 4.0 contact card with the details from basics
+	#[cfg_attr(feature = "clap", value(alias = "vcard"))]
+	Vcf,
+	/// Europass CV XML, for applications within the EU
+	Europass,
+}
+
+impl Format {
+	pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+		let ext = path
+			.extension()
+			.and_then(|x| x.to_str())
+			.ok_or(anyhow!("could not get extension"))?;
+
+		match ext {
+			"html" | "htm" => Ok(Format::Html),
+			"pdf" => Ok(Format::Pdf),
+			"md" | "markdown" => Ok(Format::Markdown),
+			"txt" | "text" => Ok(Format::Text),
+			"tex" | "latex" => Ok(Format::Latex),
+			"typ" => Ok(Format::Typst),
+			"docx" => Ok(Format::Docx),
+			"odt" => Ok(Format::Odt),
+			"vcf" | "vcard" => Ok(Format::Vcf),
+			"xml" => Ok(Format::Europass),
+			_ => bail!("unknown extension {} for file {:?}", ext, path),
+		}
+	}
+}
@@ -0,0 +1,208 @@
+use std::fmt::Write;
+
+use rsb_schema::Resume;
+
+use crate::{
+	outline::{Entry, Header, Link, Outline, Section},
+	Options,
+};
+
+/// Bundled article-based layout, so the output only needs packages shipped with every TeX distribution.
+/// Builds with pdflatex as well as xelatex and lualatex, which take Unicode text through fontspec
+const PREAMBLE: &str = r"\documentclass[10pt,a4paper]{article}
+\usepackage{iftex}
+\ifPDFTeX
+	\usepackage[utf8]{inputenc}
+	\usepackage[T1]{fontenc}
+	\usepackage{lmodern}
+\else
+	\usepackage{fontspec}
+\fi
+\usepackage[margin=18mm]{geometry}
+\usepackage{enumitem}
+\usepackage[hidelinks]{hyperref}
+
+\pagestyle{empty}
+\setlength{\parindent}{0pt}
+\setlist[itemize]{leftmargin=*,nosep}
+
+\newcommand{\resumesection}[1]{%
+	\par\bigskip{\large\bfseries #1}\par\vspace{-0.5em}\rule{\linewidth}{0.4pt}\par}
+\newcommand{\resumeheading}[2]{\par\smallskip\textbf{#1}\hfill\textbf{#2}\par}
+\newcommand{\resumesubheading}[2]{\textit{#1}\hfill #2\par}
+";
+
+/// Escapes every character with a special meaning in LaTeX text mode
+fn escape(text: &str) -> String {
+	let mut res = String::with_capacity(text.len());
+	for c in text.chars() {
+		match c {
+			'\\' => res.push_str(r"\textbackslash{}"),
+			'{' => res.push_str(r"\{"),
+			'}' => res.push_str(r"\}"),
+			'$' => res.push_str(r"\$"),
+			'&' => res.push_str(r"\&"),
+			'#' => res.push_str(r"\#"),
+			'%' => res.push_str(r"\%"),
+			'_' => res.push_str(r"\_"),
+			'^' => res.push_str(r"\textasciicircum{}"),
+			'~' => res.push_str(r"\textasciitilde{}"),
+			'<' => res.push_str(r"\textless{}"),
+			'>' => res.push_str(r"\textgreater{}"),
+			'|' => res.push_str(r"\textbar{}"),
+			// skill level indicator, not in the T1 encoding
+			'●' => res.push_str(r"\textbullet{}"),
+			'○' => res.push_str(r"$\circ$"),
+			// keeps a leading bracket from being read as an optional argument, e.g. after \item
+			'[' => res.push_str("{[}"),
+			']' => res.push_str("{]}"),
+			_ => res.push(c),
+		}
+	}
+	res
+}
+
+/// Escapes a URL for use as the first argument of `\href`
+fn escape_url(url: &str) -> String {
+	let mut res = String::with_capacity(url.len());
+	for c in url.chars() {
+		match c {
+			'\\' => res.push_str("%5C"),
+			'{' => res.push_str("%7B"),
+			'}' => res.push_str("%7D"),
+			' ' => res.push_str("%20"),
+			'%' => res.push_str(r"\%"),
+			'#' => res.push_str(r"\#"),
+			// a link inside another command's argument, e.g. \resumeheading, reads `~` as a non-breaking space
+			'~' => res.push_str("%7E"),
+			_ => res.push(c),
+		}
+	}
+	res
+}
+
+fn link(link: &Link) -> String {
+	match &link.url {
+		Some(url) => format!(r"\href{{{}}}{{{}}}", escape_url(url), escape(&link.text)),
+		None => escape(&link.text),
+	}
+}
+
+fn opt(text: Option<&str>) -> String {
+	text.map(escape).unwrap_or_default()
+}
+
+fn header(out: &mut String, header: Header) -> std::fmt::Result {
+	writeln!(out, r"\begin{{center}}")?;
+	if let Some(name) = &header.name {
+		writeln!(out, r"{{\LARGE\bfseries {}}}\par\smallskip", escape(name))?;
+	}
+	if let Some(label) = &header.label {
+		writeln!(out, r"\textit{{{}}}\par\smallskip", escape(label))?;
+	}
+
+	let contacts: Vec<String> = header
+		.contacts
+		.iter()
+		.map(link)
+		.chain(header.location.as_deref().map(escape))
+		.collect();
+	if !contacts.is_empty() {
+		writeln!(out, "{}", contacts.join(r" \textbar{} "))?;
+	}
+	writeln!(out, r"\end{{center}}")?;
+
+	if let Some(summary) = &header.summary {
+		writeln!(out, "\n{}\n", escape(summary))?;
+	}
+	Ok(())
+}
+
+fn entry(out: &mut String, entry: Entry) -> std::fmt::Result {
+	writeln!(out)?;
+	if entry.title.is_some() || entry.date.is_some() {
+		let title = entry.title.as_ref().map(link).unwrap_or_default();
+		writeln!(out, r"\resumeheading{{{}}}{{{}}}", title, opt(entry.date.as_deref()))?;
+	}
+	if entry.subtitle.is_some() || entry.location.is_some() {
+		writeln!(
+			out,
+			r"\resumesubheading{{{}}}{{{}}}",
+			entry.subtitle.as_ref().map(link).unwrap_or_default(),
+			opt(entry.location.as_deref())
+		)?;
+	}
+
+	for paragraph in &entry.paragraphs {
+		writeln!(out, r"{}\par", escape(paragraph))?;
+	}
+
+	if !entry.highlights.is_empty() {
+		writeln!(out, r"\begin{{itemize}}")?;
+		for highlight in &entry.highlights {
+			writeln!(out, r"	\item {}", escape(highlight))?;
+		}
+		writeln!(out, r"\end{{itemize}}")?;
+	}
+
+	if !entry.keywords.is_empty() {
+		let keywords: Vec<String> = entry.keywords.iter().map(|k| escape(k)).collect();
+		writeln!(out, r"\textit{{Keywords:}} {}\par", keywords.join(", "))?;
+	}
+	Ok(())
+}
+
+fn section(out: &mut String, section: Section) -> std::fmt::Result {
+	writeln!(out, "\n\\resumesection{{{}}}", section.kind.title())?;
+	for item in section.entries {
+		entry(out, item)?;
+	}
+	Ok(())
+}
+
+/// Renders `val` into a standalone `.tex` document
+pub fn generate(val: Resume, opts: Options) -> anyhow::Result<String> {
+	let outline = Outline::new(val, &opts);
+	let mut out = String::from(PREAMBLE);
+
+	match outline.header.name.as_deref() {
+		Some(name) => writeln!(out, r"\hypersetup{{pdftitle={{{0}}}, pdfauthor={{{0}}}}}", escape(name))?,
+		None => writeln!(out, r"\hypersetup{{pdftitle={{Resume}}}}")?,
+	}
+	writeln!(out, "\n\\begin{{document}}\n")?;
+
+	header(&mut out, outline.header)?;
+	for item in outline.sections {
+		section(&mut out, item)?;
+	}
+
+	writeln!(out, "\n\\end{{document}}")?;
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn escape_special_characters() {
+		assert_eq!(escape(r"50% of $5 & #1_a"), r"50\% of \$5 \& \#1\_a");
+		assert_eq!(escape(r"{a}\b"), r"\{a\}\textbackslash{}b");
+		assert_eq!(
+			escape("~^|<>"),
+			r"\textasciitilde{}\textasciicircum{}\textbar{}\textless{}\textgreater{}"
+		);
+		assert_eq!(escape("[1]"), "{[}1{]}");
+		assert_eq!(escape("●○"), r"\textbullet{}$\circ$");
+		assert_eq!(escape("Zoë"), "Zoë");
+	}
+
+	#[test]
+	fn escape_url_percent_encodes() {
+		assert_eq!(
+			escape_url(r"https://a.com/~me/a b?q=100%#top"),
+			r"https://a.com/%7Eme/a%20b?q=100\%\#top"
+		);
+		assert_eq!(escape_url(r"https://a.com/{x}\y"), "https://a.com/%7Bx%7D%5Cy");
+	}
+}
@@ -0,0 +1,44 @@
+//! Skill levels on a fixed scale, for the optional level indicator
+
+pub const MAX_LEVEL: u8 = 5;
+
+/// Best-effort mapping of a free-form level (e.g. "Master", "3/5", "80%") onto `0..=MAX_LEVEL`
+pub fn level_score(level: &str) -> Option<u8> {
+	let level = level.trim().to_lowercase();
+
+	let named = match level.as_str() {
+		"beginner" | "novice" | "basic" | "elementary" => Some(1),
+		"intermediate" | "familiar" | "working" => Some(2),
+		"proficient" | "competent" => Some(3),
+		"advanced" | "fluent" => Some(4),
+		"expert" | "master" | "native" => Some(5),
+		_ => None,
+	};
+	if named.is_some() {
+		return named;
+	}
+
+	if let Some((value, max)) = level.split_once('/') {
+		let value: f32 = value.trim().parse().ok()?;
+		let max: f32 = max.trim().parse().ok()?;
+		if max <= 0.0 || value < 0.0 || value > max {
+			return None;
+		}
+		return Some((value / max * MAX_LEVEL as f32).round() as u8);
+	}
+
+	if let Some(percent) = level.strip_suffix('%') {
+		let percent: f32 = percent.trim().parse().ok()?;
+		if !(0.0..=100.0).contains(&percent) {
+			return None;
+		}
+		return Some((percent / 100.0 * MAX_LEVEL as f32).round() as u8);
+	}
+
+	None
+}
+
+/// Text form of the indicator for outputs without styling, e.g. `●●●○○` for 3
+pub fn indicator_text(score: u8) -> String {
+	(0..MAX_LEVEL).map(|i| if i < score { '●' } else { '○' }).collect()
+}
@@ -1,18 +1,135 @@
+mod citation;
 mod components;
+mod dates;
+mod docx;
+mod europass;
+mod format;
+mod latex;
+mod levels;
+mod markdown;
+mod odt;
+mod options;
+mod outline;
+mod pdf;
+mod plaintext;
+mod text;
+mod typst;
+mod vcard;
+mod xml;
 
+pub use europass::CEFR_SKILLS;
+pub use format::Format;
 use hypertext::{html_elements, maud, Renderable};
+pub use options::{CitationStyle, Options};
 use rsb_schema::Resume;
 
-pub fn generate(val: Resume) -> anyhow::Result<String> {
+pub fn generate(val: Resume, opts: Options) -> anyhow::Result<String> {
 	log::debug!("Templating with values:\n{:#?}", val);
+	log::debug!("Templating with options: {:?}", opts);
+	let name = val.basics.name.clone();
+	let date = dates::document_date(opts.date, val.meta.last_modified.as_deref());
 	let mut res = String::new();
 	maud! {
 		!DOCTYPE
 		html {
-			(components::Head(val.basics.name.clone()))
-			(components::Body(val))
+			(components::Head(name, date, components::JsonLd::from(&val)))
+			(components::Body(val, opts))
 		}
 	}
 	.render_to(&mut res);
 	Ok(res)
 }
+
+/// Render `val` into the bytes of the given output format
+pub fn render(val: Resume, format: Format, opts: Options) -> anyhow::Result<Vec<u8>> {
+	match format {
+		Format::Html => Ok(generate(val, opts)?.into_bytes()),
+		Format::Pdf => pdf::generate(val, opts),
+		Format::Markdown => Ok(markdown::generate(val, opts)?.into_bytes()),
+		Format::Text => Ok(plaintext::generate(val, opts)?.into_bytes()),
+		Format::Latex => Ok(latex::generate(val, opts)?.into_bytes()),
+		Format::Typst => Ok(typst::generate(val, opts)?.into_bytes()),
+		Format::Docx => docx::generate(val, opts),
+		Format::Odt => odt::generate(val, opts),
+		Format::Vcf => Ok(vcard::generate(val.basics)?.into_bytes()),
+		Format::Europass => Ok(europass::generate(val)?.into_bytes()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Cursor;
+
+	use chrono::NaiveDate;
+	use zip::ZipArchive;
+
+	use super::*;
+
+	const FORMATS: [Format; 10] = [
+		Format::Html,
+		Format::Pdf,
+		Format::Markdown,
+		Format::Text,
+		Format::Latex,
+		Format::Typst,
+		Format::Docx,
+		Format::Odt,
+		Format::Vcf,
+		Format::Europass,
+	];
+
+	fn resume() -> Resume {
+		serde_json::from_str(
+			r#"{
+				"basics": {"name": "Jane Doe", "email": "jane@example.com", "url": "https://example.com"},
+				"work": [{"name": "Acme", "url": "https://acme.example", "startDate": "2020-01"}],
+				"skills": [{"name": "Rust", "level": "Advanced"}],
+				"publications": [{"name": "Paper", "url": "https://doi.example", "releaseDate": "2021"}]
+			}"#,
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn every_format_is_reproducible() {
+		let opts = Options {
+			date: NaiveDate::from_ymd_opt(2024, 2, 3),
+			..Default::default()
+		};
+		for format in FORMATS {
+			let first = render(resume(), format, opts.clone()).unwrap();
+			let second = render(resume(), format, opts.clone()).unwrap();
+			assert!(first == second, "{:?} output differs between runs", format);
+		}
+	}
+
+	#[test]
+	fn zip_entries_have_a_fixed_timestamp() {
+		for format in [Format::Docx, Format::Odt] {
+			let data = render(resume(), format, Options::default()).unwrap();
+			let mut archive = ZipArchive::new(Cursor::new(data)).unwrap();
+			for i in 0..archive.len() {
+				let entry = archive.by_index(i).unwrap();
+				assert_eq!(
+					entry.last_modified(),
+					Some(zip::DateTime::default()),
+					"{:?} {}",
+					format,
+					entry.name()
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn pdf_metadata_uses_document_date() {
+		let opts = Options {
+			date: NaiveDate::from_ymd_opt(2024, 2, 3),
+			..Default::default()
+		};
+		let pdf = render(resume(), Format::Pdf, opts).unwrap();
+		let pdf = String::from_utf8_lossy(&pdf);
+		assert!(pdf.contains("/CreationDate(D:20240203000000+00'00')"));
+		assert!(pdf.contains("/ModDate(D:20240203000000+00'00')"));
+	}
+}
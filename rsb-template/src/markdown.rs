@@ -0,0 +1,227 @@
+use std::fmt::Write;
+
+use rsb_schema::Resume;
+
+use crate::{
+	outline::{Entry, Header, Link, Outline, Section},
+	Options,
+};
+
+/// Backslash-escapes characters that CommonMark would treat as inline markup. Line breaks become spaces, as
+/// inline text has to stay on one line
+fn escape(text: &str) -> String {
+	let mut res = String::with_capacity(text.len());
+	for c in text.chars() {
+		match c {
+			'\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~' | '!' | '&' => {
+				res.push('\\');
+				res.push(c);
+			}
+			'\n' => res.push(' '),
+			'\r' => {}
+			_ => res.push(c),
+		}
+	}
+	res
+}
+
+/// A code span around `text`, with a fence longer than any run of backticks inside it so that none needs changing
+fn code(text: &str) -> String {
+	let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or_default();
+	let fence = "`".repeat(longest + 1);
+	// a space keeps a backtick at either end from joining the fence, and is stripped again when rendered
+	let pad = match text.starts_with('`') || text.ends_with('`') {
+		true => " ",
+		false => "",
+	};
+	let text = text.replace(['\n', '\r'], " ");
+	format!("{}{}{}{}{}", fence, pad, text, pad, fence)
+}
+
+/// Like [`escape`], but also stops text at the start of a block from turning into a list or heading
+fn escape_block(text: &str) -> String {
+	let res = escape(text);
+	let digits = res.chars().take_while(char::is_ascii_digit).count();
+
+	match res.chars().nth(digits) {
+		Some('.' | ')') if digits > 0 => format!("{}\\{}", &res[..digits], &res[digits..]),
+		Some('-' | '+' | '=') if digits == 0 => format!("\\{}", res),
+		_ => res,
+	}
+}
+
+/// Paragraphs of `text`, split at blank lines. Single line breaks become hard breaks, with `indent` before each
+/// continuation line
+fn blocks(text: &str, indent: &str) -> Vec<String> {
+	let separator = format!("\\\n{}", indent);
+	let mut blocks = Vec::new();
+	let mut lines: Vec<String> = Vec::new();
+	for line in text.lines().map(str::trim).chain([""]) {
+		match line.is_empty() {
+			true if !lines.is_empty() => blocks.push(std::mem::take(&mut lines).join(&separator)),
+			true => {}
+			false => lines.push(escape_block(line)),
+		}
+	}
+	blocks
+}
+
+/// `*markup*`. Emphasis does not work with whitespace just inside the markers, so the markup is trimmed first
+fn emphasis(markup: &str) -> Option<String> {
+	let text = markup.trim();
+	match text.is_empty() {
+		true => None,
+		false => Some(format!("*{}*", text)),
+	}
+}
+
+fn link(link: &Link) -> String {
+	match &link.url {
+		Some(url) => {
+			let url = url.replace(' ', "%20").replace('(', "%28").replace(')', "%29");
+			format!("[{}]({})", escape(&link.text), url)
+		}
+		None => escape(&link.text),
+	}
+}
+
+fn header(out: &mut String, header: Header) -> std::fmt::Result {
+	if let Some(name) = header.name {
+		writeln!(out, "# {}\n", escape(&name))?;
+	}
+	if let Some(label) = header.label.as_deref().map(escape).as_deref().and_then(emphasis) {
+		writeln!(out, "{}\n", label)?;
+	}
+
+	let contacts: Vec<String> = header
+		.contacts
+		.iter()
+		.map(link)
+		.chain(header.location.as_deref().map(escape))
+		.collect();
+	if !contacts.is_empty() {
+		writeln!(out, "{}\n", contacts.join(" · "))?;
+	}
+
+	for paragraph in blocks(header.summary.as_deref().unwrap_or_default(), "") {
+		writeln!(out, "{}\n", paragraph)?;
+	}
+	Ok(())
+}
+
+fn entry(out: &mut String, entry: Entry) -> std::fmt::Result {
+	let heading: Vec<String> = entry
+		.title
+		.as_ref()
+		.map(link)
+		.into_iter()
+		.chain(entry.date.as_deref().map(escape))
+		.collect();
+	if !heading.is_empty() {
+		writeln!(out, "### {}\n", heading.join(" — "))?;
+	}
+
+	let details: Vec<String> = entry
+		.subtitle
+		.as_ref()
+		.and_then(|subtitle| emphasis(&link(subtitle)))
+		.into_iter()
+		.chain(entry.location.as_deref().map(escape))
+		.collect();
+	if !details.is_empty() {
+		writeln!(out, "{}\n", details.join(" · "))?;
+	}
+
+	for paragraph in entry.paragraphs.iter().flat_map(|paragraph| blocks(paragraph, "")) {
+		writeln!(out, "{}\n", paragraph)?;
+	}
+
+	// blank lines inside an item would make the list loose, so a highlight is always a single block
+	let highlights: Vec<String> = entry
+		.highlights
+		.iter()
+		.map(|highlight| blocks(highlight, "  ").join("\\\n  "))
+		.filter(|highlight| !highlight.is_empty())
+		.collect();
+	if !highlights.is_empty() {
+		for highlight in highlights {
+			writeln!(out, "- {}", highlight)?;
+		}
+		writeln!(out)?;
+	}
+
+	if !entry.keywords.is_empty() {
+		let keywords: Vec<String> = entry.keywords.iter().map(|k| code(k)).collect();
+		writeln!(out, "**Keywords:** {}\n", keywords.join(", "))?;
+	}
+	Ok(())
+}
+
+fn section(out: &mut String, section: Section) -> std::fmt::Result {
+	writeln!(out, "## {}\n", section.kind.title())?;
+	for item in section.entries {
+		entry(out, item)?;
+	}
+	Ok(())
+}
+
+/// Renders `val` as a CommonMark document
+pub fn generate(val: Resume, opts: Options) -> anyhow::Result<String> {
+	let outline = Outline::new(val, &opts);
+	let mut out = String::new();
+
+	header(&mut out, outline.header)?;
+	for item in outline.sections {
+		section(&mut out, item)?;
+	}
+
+	// a single trailing newline, however the last block ended
+	out.truncate(out.trim_end().len());
+	out.push('\n');
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn escape_inline_markup() {
+		assert_eq!(
+			escape("a*b_c `d` [e](f) <g> #h |i| ~j~ !k"),
+			r"a\*b\_c \`d\` \[e\](f) \<g\> \#h \|i\| \~j\~ \!k"
+		);
+		assert_eq!(escape(r"AT&amp;T \o/"), r"AT\&amp;T \\o/");
+		assert_eq!(escape("two\r\nlines"), "two lines");
+	}
+
+	#[test]
+	fn escape_block_starts() {
+		assert_eq!(escape_block("1. first"), r"1\. first");
+		assert_eq!(escape_block("2020) year"), r"2020\) year");
+		assert_eq!(escape_block("- item"), r"\- item");
+		assert_eq!(escape_block("2020 - now"), "2020 - now");
+	}
+
+	#[test]
+	fn emphasis_is_trimmed() {
+		assert_eq!(emphasis(" Engineer "), Some(String::from("*Engineer*")));
+		assert_eq!(emphasis(&escape(r"back\ ")), Some(String::from(r"*back\\*")));
+		assert_eq!(emphasis("  "), None);
+	}
+
+	#[test]
+	fn code_keeps_backticks() {
+		assert_eq!(code("Rust"), "`Rust`");
+		assert_eq!(code("a`b"), "``a`b``");
+		assert_eq!(code("``x"), "``` ``x ```");
+		assert_eq!(code("a*b_c"), "`a*b_c`");
+	}
+
+	#[test]
+	fn blocks_keep_line_breaks() {
+		assert_eq!(blocks("one\ntwo\n\n- three\n", ""), ["one\\\ntwo", r"\- three"]);
+		assert_eq!(blocks("one\ntwo", "  "), ["one\\\n  two"]);
+		assert!(blocks(" \n ", "").is_empty());
+	}
+}
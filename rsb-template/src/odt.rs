@@ -0,0 +1,323 @@
+use std::{
+	fmt::Write as _,
+	io::{Cursor, Write as _},
+};
+
+use rsb_schema::{Basics, Resume};
+use zip::{write::SimpleFileOptions, CompressionMethod, DateTime, ZipWriter};
+
+use crate::{
+	outline::{Entry, Header, Link, Outline, Section},
+	xml::{dublin_core, escape},
+	Options,
+};
+
+const MIMETYPE: &str = "application/vnd.oasis.opendocument.text";
+
+const MANIFEST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.3">
+<manifest:file-entry manifest:full-path="/" manifest:version="1.3" manifest:media-type="application/vnd.oasis.opendocument.text"/>
+<manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+<manifest:file-entry manifest:full-path="styles.xml" manifest:media-type="text/xml"/>
+<manifest:file-entry manifest:full-path="meta.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>
+"#;
+
+/// A4 with 18mm margins. Entry dates are right aligned on a tab stop at the right margin
+const STYLES: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-styles xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" xmlns:svg="urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0" office:version="1.3">
+<office:font-face-decls>
+<style:font-face style:name="DejaVu Serif" svg:font-family="'DejaVu Serif'" style:font-family-generic="roman"/>
+</office:font-face-decls>
+<office:styles>
+<style:default-style style:family="paragraph">
+<style:paragraph-properties fo:margin-top="0mm" fo:margin-bottom="1mm"/>
+<style:text-properties style:font-name="DejaVu Serif" fo:font-size="10pt"/>
+</style:default-style>
+<style:style style:name="Standard" style:family="paragraph" style:class="text"/>
+<style:style style:name="Title" style:family="paragraph" style:parent-style-name="Standard" style:class="chapter">
+<style:paragraph-properties fo:text-align="center"/>
+<style:text-properties fo:font-size="20pt" fo:font-weight="bold"/>
+</style:style>
+<style:style style:name="Subtitle" style:family="paragraph" style:parent-style-name="Standard" style:class="chapter">
+<style:paragraph-properties fo:text-align="center"/>
+<style:text-properties fo:font-style="italic"/>
+</style:style>
+<style:style style:name="Contacts" style:family="paragraph" style:parent-style-name="Standard">
+<style:paragraph-properties fo:text-align="center" fo:margin-bottom="3mm"/>
+</style:style>
+<style:style style:name="Heading_20_1" style:display-name="Heading 1" style:family="paragraph" style:parent-style-name="Standard" style:next-style-name="Standard" style:default-outline-level="1" style:class="text">
+<style:paragraph-properties fo:margin-top="4mm" fo:margin-bottom="1.5mm" fo:keep-with-next="always" fo:border-bottom="0.5pt solid #000000" fo:padding-bottom="0.5mm"/>
+<style:text-properties fo:font-size="12pt" fo:font-weight="bold"/>
+</style:style>
+<style:style style:name="Heading_20_2" style:display-name="Heading 2" style:family="paragraph" style:parent-style-name="Standard" style:next-style-name="Standard" style:default-outline-level="2" style:class="text">
+<style:paragraph-properties fo:margin-top="2mm" fo:margin-bottom="0mm" fo:keep-with-next="always">
+<style:tab-stops><style:tab-stop style:position="174mm" style:type="right"/></style:tab-stops>
+</style:paragraph-properties>
+<style:text-properties fo:font-weight="bold"/>
+</style:style>
+<style:style style:name="Entry_20_Details" style:display-name="Entry Details" style:family="paragraph" style:parent-style-name="Standard" style:class="text">
+<style:paragraph-properties fo:keep-with-next="always">
+<style:tab-stops><style:tab-stop style:position="174mm" style:type="right"/></style:tab-stops>
+</style:paragraph-properties>
+</style:style>
+<style:style style:name="List_20_Bullet" style:display-name="List Bullet" style:family="paragraph" style:parent-style-name="Standard" style:list-style-name="Bullets" style:class="list">
+<style:paragraph-properties fo:margin-bottom="0mm"/>
+</style:style>
+<style:style style:name="Emphasis" style:family="text">
+<style:text-properties fo:font-style="italic"/>
+</style:style>
+<style:style style:name="Internet_20_link" style:display-name="Internet link" style:family="text">
+<style:text-properties fo:color="#0563c1" style:text-underline-style="solid" style:text-underline-width="auto" style:text-underline-color="font-color"/>
+</style:style>
+<text:list-style style:name="Bullets">
+<text:list-level-style-bullet text:level="1" text:bullet-char="•">
+<style:list-level-properties text:list-level-position-and-space-mode="label-alignment">
+<style:list-level-label-alignment text:label-followed-by="listtab" text:list-tab-stop-position="5mm" fo:text-indent="-5mm" fo:margin-left="5mm"/>
+</style:list-level-properties>
+</text:list-level-style-bullet>
+</text:list-style>
+</office:styles>
+<office:automatic-styles>
+<style:page-layout style:name="PageLayout">
+<style:page-layout-properties fo:page-width="210mm" fo:page-height="297mm" fo:margin-top="18mm" fo:margin-bottom="18mm" fo:margin-left="18mm" fo:margin-right="18mm"/>
+</style:page-layout>
+</office:automatic-styles>
+<office:master-styles>
+<style:master-page style:name="Standard" style:page-layout-name="PageLayout"/>
+</office:master-styles>
+</office:document-styles>
+"##;
+
+/// `count` spaces that survive ODF whitespace collapsing
+fn spaces(count: usize) -> String {
+	match count {
+		1 => String::from("<text:s/>"),
+		n => format!(r#"<text:s text:c="{}"/>"#, n),
+	}
+}
+
+/// Text with line breaks and tabs turned into their ODF elements. Leading, trailing and repeated spaces are kept as
+/// `<text:s/>`, as ODF collapses them otherwise
+fn text(text: &str) -> String {
+	let mut res = String::with_capacity(text.len());
+	let mut count = 0;
+	// at the start of the text or of a line, where even a single space is dropped
+	let mut leading = true;
+	for c in escape(text).chars() {
+		if c == ' ' {
+			count += 1;
+			continue;
+		}
+		match count {
+			0 => {}
+			n if leading => res.push_str(&spaces(n)),
+			1 => res.push(' '),
+			n => {
+				res.push(' ');
+				res.push_str(&spaces(n - 1));
+			}
+		}
+		count = 0;
+		match c {
+			'\n' => res.push_str("<text:line-break/>"),
+			'\t' => res.push_str("<text:tab/>"),
+			'\r' => continue,
+			_ => res.push(c),
+		}
+		leading = matches!(c, '\n' | '\t');
+	}
+	if count > 0 {
+		res.push_str(&spaces(count));
+	}
+	res
+}
+
+fn link(link: &Link) -> String {
+	match &link.url {
+		Some(url) => format!(
+			r#"<text:a xlink:type="simple" xlink:href="{}" text:style-name="Internet_20_link">{}</text:a>"#,
+			escape(url),
+			text(&link.text)
+		),
+		None => text(&link.text),
+	}
+}
+
+fn paragraph(out: &mut String, style: &str, content: &str) -> std::fmt::Result {
+	writeln!(out, r#"<text:p text:style-name="{}">{}</text:p>"#, style, content)
+}
+
+fn heading(out: &mut String, style: &str, level: u8, content: &str) -> std::fmt::Result {
+	writeln!(
+		out,
+		r#"<text:h text:style-name="{}" text:outline-level="{}">{}</text:h>"#,
+		style, level, content
+	)
+}
+
+fn header(out: &mut String, header: Header) -> std::fmt::Result {
+	if let Some(name) = &header.name {
+		paragraph(out, "Title", &text(name))?;
+	}
+	if let Some(label) = &header.label {
+		paragraph(out, "Subtitle", &text(label))?;
+	}
+
+	let contacts: Vec<String> = header
+		.contacts
+		.iter()
+		.map(link)
+		.chain(header.location.as_deref().map(text))
+		.collect();
+	if !contacts.is_empty() {
+		paragraph(out, "Contacts", &contacts.join(" | "))?;
+	}
+
+	if let Some(summary) = &header.summary {
+		paragraph(out, "Standard", &text(summary))?;
+	}
+	Ok(())
+}
+
+fn entry(out: &mut String, entry: Entry) -> std::fmt::Result {
+	if entry.title.is_some() || entry.date.is_some() {
+		let mut content = entry.title.as_ref().map(link).unwrap_or_default();
+		if let Some(date) = &entry.date {
+			write!(content, "<text:tab/>{}", text(date))?;
+		}
+		heading(out, "Heading_20_2", 2, &content)?;
+	}
+
+	if entry.subtitle.is_some() || entry.location.is_some() {
+		let mut content = entry
+			.subtitle
+			.as_ref()
+			.map(|subtitle| {
+				format!(
+					r#"<text:span text:style-name="Emphasis">{}</text:span>"#,
+					link(subtitle)
+				)
+			})
+			.unwrap_or_default();
+		if let Some(location) = &entry.location {
+			write!(content, "<text:tab/>{}", text(location))?;
+		}
+		paragraph(out, "Entry_20_Details", &content)?;
+	}
+
+	for item in &entry.paragraphs {
+		paragraph(out, "Standard", &text(item))?;
+	}
+
+	if !entry.highlights.is_empty() {
+		writeln!(out, r#"<text:list text:style-name="Bullets">"#)?;
+		for highlight in &entry.highlights {
+			write!(out, "<text:list-item>")?;
+			paragraph(out, "List_20_Bullet", &text(highlight))?;
+			writeln!(out, "</text:list-item>")?;
+		}
+		writeln!(out, "</text:list>")?;
+	}
+
+	if !entry.keywords.is_empty() {
+		let content = format!(
+			r#"<text:span text:style-name="Emphasis">Keywords:</text:span> {}"#,
+			text(&entry.keywords.join(", "))
+		);
+		paragraph(out, "Standard", &content)?;
+	}
+	Ok(())
+}
+
+fn section(out: &mut String, section: Section) -> std::fmt::Result {
+	heading(out, "Heading_20_1", 1, &text(section.kind.title()))?;
+	for item in section.entries {
+		entry(out, item)?;
+	}
+	Ok(())
+}
+
+fn content_xml(outline: Outline) -> Result<String, std::fmt::Error> {
+	let mut out = String::from(concat!(
+		r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+		"\n",
+		r#"<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" "#,
+		r#"xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" "#,
+		r#"xmlns:xlink="http://www.w3.org/1999/xlink" office:version="1.3">"#,
+		"\n<office:body>\n<office:text>\n"
+	));
+
+	header(&mut out, outline.header)?;
+	for item in outline.sections {
+		section(&mut out, item)?;
+	}
+
+	out.push_str("</office:text>\n</office:body>\n</office:document-content>\n");
+	Ok(out)
+}
+
+fn meta_xml(basics: &Basics) -> String {
+	let mut res = String::from(concat!(
+		r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+		"\n",
+		r#"<office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" "#,
+		r#"xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" "#,
+		r#"xmlns:dc="http://purl.org/dc/elements/1.1/" office:version="1.3">"#,
+		"\n<office:meta>\n<meta:generator>rsb</meta:generator>\n"
+	));
+	if let Some(name) = &basics.name {
+		res.push_str(&format!(
+			"<meta:initial-creator>{}</meta:initial-creator>\n",
+			escape(name)
+		));
+	}
+	res.push_str(&dublin_core(basics));
+	res.push_str("</office:meta>\n</office:document-meta>\n");
+	res
+}
+
+/// Renders `val` into an OpenDocument text (`.odt`) package
+pub fn generate(val: Resume, opts: Options) -> anyhow::Result<Vec<u8>> {
+	let meta = meta_xml(&val.basics);
+	let content = content_xml(Outline::new(val, &opts))?;
+
+	// a fixed timestamp, as zip falls back to the wall clock when its time feature is on
+	let options = SimpleFileOptions::default().last_modified_time(DateTime::default());
+	let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+	// the mimetype must come first and stay uncompressed, so the file type can be sniffed at a fixed offset
+	zip.start_file("mimetype", options.compression_method(CompressionMethod::Stored))?;
+	zip.write_all(MIMETYPE.as_bytes())?;
+
+	let parts = [
+		("META-INF/manifest.xml", String::from(MANIFEST)),
+		("meta.xml", meta),
+		("styles.xml", String::from(STYLES)),
+		("content.xml", content),
+	];
+	let options = options.compression_method(CompressionMethod::Deflated);
+	for (name, data) in parts {
+		zip.start_file(name, options)?;
+		zip.write_all(data.as_bytes())?;
+	}
+	Ok(zip.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn text_keeps_spaces() {
+		assert_eq!(text("a b"), "a b");
+		assert_eq!(text("a   b"), r#"a <text:s text:c="2"/>b"#);
+		assert_eq!(text(" a"), "<text:s/>a");
+		assert_eq!(text("   a "), r#"<text:s text:c="3"/>a<text:s/>"#);
+	}
+
+	#[test]
+	fn text_turns_breaks_into_elements() {
+		assert_eq!(text("a\r\n b\tc"), "a<text:line-break/><text:s/>b<text:tab/>c");
+		assert_eq!(text("<a & b>"), "&lt;a &amp; b&gt;");
+	}
+}
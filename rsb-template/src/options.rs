@@ -0,0 +1,50 @@
+use chrono::NaiveDate;
+
+/// Ordering used to format publication entries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum CitationStyle {
+	/// (year). title. publisher.
+	#[default]
+	Apa,
+	/// "title," publisher, month year.
+	Ieee,
+}
+
+/// Knobs that change how a resume is rendered
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+pub struct Options {
+	#[cfg_attr(
+		feature = "clap",
+		arg(long, help = "show skill levels as a visual indicator next to the level text")
+	)]
+	pub skill_level_indicator: bool,
+	#[cfg_attr(
+		feature = "clap",
+		arg(long, value_enum, default_value_t, help = "citation style used for publications")
+	)]
+	pub citation_style: CitationStyle,
+	#[cfg_attr(
+		feature = "clap",
+		arg(long, help = "hide reference names and quotes, noting they are available on request")
+	)]
+	pub references_on_request: bool,
+	#[cfg_attr(
+		feature = "clap",
+		arg(
+			long,
+			help = "render all sections, with empty ones as headings to fill in. Ignored by html, vcf and europass"
+		)
+	)]
+	pub all_sections: bool,
+	#[cfg_attr(
+		feature = "clap",
+		arg(
+			long,
+			value_name = "YYYY-MM-DD",
+			help = "document date in the HTML title and PDF metadata. Default: SOURCE_DATE_EPOCH, then meta.lastModified"
+		)
+	)]
+	pub date: Option<NaiveDate>,
+}
@@ -0,0 +1,439 @@
+//! A format-agnostic view of a [`Resume`], shared by the non-HTML backends.
+//!
+//! Every section is flattened into [`Entry`] values so a backend only has to know how to lay out a
+//! header, a section heading and an entry.
+
+use rsb_schema::{
+	AwardsItem, Basics, CertificatesItem, EducationItem, InterestsItem, LanguagesItem, ProjectsItem, PublicationsItem,
+	ReferencesItem, Resume, SkillsItem, VolunteerItem, WorkItem,
+};
+
+use crate::{
+	citation::{apa_date, ieee_date, terminated},
+	dates::{format_date, format_range},
+	levels::{indicator_text, level_score},
+	text, CitationStyle, Options,
+};
+
+/// Text with an optional link target
+#[derive(Debug, Clone)]
+pub struct Link {
+	pub text: String,
+	pub url: Option<String>,
+}
+
+impl From<String> for Link {
+	fn from(text: String) -> Self {
+		Link { text, url: None }
+	}
+}
+
+impl Link {
+	fn new(text: Option<String>, url: Option<String>) -> Option<Self> {
+		match (text, url) {
+			(Some(text), url) => Some(Link { text, url }),
+			(None, Some(url)) => Some(Link {
+				text: url.clone(),
+				url: Some(url),
+			}),
+			(None, None) => None,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionKind {
+	Education,
+	Work,
+	Publications,
+	Projects,
+	Skills,
+	Awards,
+	Certificates,
+	Volunteer,
+	Interests,
+	Languages,
+	References,
+}
+
+impl SectionKind {
+	pub fn title(&self) -> &'static str {
+		match self {
+			SectionKind::Education => "Education",
+			SectionKind::Work => "Work",
+			SectionKind::Publications => "Publications",
+			SectionKind::Projects => "Projects",
+			SectionKind::Skills => "Skills",
+			SectionKind::Awards => "Awards",
+			SectionKind::Certificates => "Certificates",
+			SectionKind::Volunteer => "Volunteer",
+			SectionKind::Interests => "Interests",
+			SectionKind::Languages => "Languages",
+			SectionKind::References => "References",
+		}
+	}
+}
+
+/// One item of a section, e.g. a job or a degree
+#[derive(Debug, Clone, Default)]
+pub struct Entry {
+	pub title: Option<Link>,
+	pub subtitle: Option<Link>,
+	pub date: Option<String>,
+	pub location: Option<String>,
+	pub paragraphs: Vec<String>,
+	pub highlights: Vec<String>,
+	pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Section {
+	pub kind: SectionKind,
+	pub entries: Vec<Entry>,
+}
+
+/// Name, label and contact details shown at the top of a resume
+#[derive(Debug, Clone, Default)]
+pub struct Header {
+	pub name: Option<String>,
+	pub label: Option<String>,
+	pub contacts: Vec<Link>,
+	pub location: Option<String>,
+	pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Outline {
+	pub header: Header,
+	pub sections: Vec<Section>,
+}
+
+fn joined(parts: &[&str]) -> Option<String> {
+	let parts: Vec<&str> = parts.iter().copied().filter(|part| !part.trim().is_empty()).collect();
+	match parts.is_empty() {
+		true => None,
+		false => Some(parts.join(", ")),
+	}
+}
+
+impl From<Basics> for Header {
+	fn from(basics: Basics) -> Self {
+		let location = basics.location;
+		let location = joined(&[
+			location.city.as_deref().unwrap_or_default(),
+			location.region.as_deref().unwrap_or_default(),
+			location.country_code.as_deref().unwrap_or_default(),
+		]);
+
+		let mut contacts = Vec::new();
+		if let Some(email) = basics.email {
+			let url = format!("mailto:{}", email);
+			contacts.push(Link {
+				text: email,
+				url: Some(url),
+			});
+		}
+		if let Some(phone) = basics.phone {
+			contacts.push(Link { text: phone, url: None });
+		}
+		contacts.extend(Link::new(None, basics.url));
+		contacts.extend(
+			basics
+				.profiles
+				.into_iter()
+				.filter_map(|profile| Link::new(text::profile(profile.network, profile.username), profile.url)),
+		);
+
+		Header {
+			name: basics.name,
+			label: basics.label,
+			contacts,
+			location,
+			summary: basics.summary,
+		}
+	}
+}
+
+impl From<EducationItem> for Entry {
+	fn from(item: EducationItem) -> Self {
+		let degree = text::degree(item.study_type, item.area);
+		let institution = Link::new(item.institution, item.url);
+		let (title, subtitle) = match degree {
+			Some(degree) => (
+				Some(Link {
+					text: degree,
+					url: None,
+				}),
+				institution,
+			),
+			None => (institution, None),
+		};
+
+		Entry {
+			title,
+			subtitle,
+			date: format_range(item.start_date.as_ref(), item.end_date.as_ref()),
+			paragraphs: item.score.into_iter().map(|score| format!("GPA: {}", score)).collect(),
+			highlights: item.courses,
+			..Default::default()
+		}
+	}
+}
+
+impl From<WorkItem> for Entry {
+	fn from(item: WorkItem) -> Self {
+		Entry {
+			title: Link::new(item.name, item.url),
+			subtitle: item.position.map(Link::from),
+			date: format_range(item.start_date.as_ref(), item.end_date.as_ref()),
+			location: item.location,
+			paragraphs: item.description.into_iter().chain(item.summary).collect(),
+			highlights: item.highlights,
+			..Default::default()
+		}
+	}
+}
+
+/// Title and date follow the citation style. The publisher is kept apart as the subtitle
+fn publication(item: PublicationsItem, style: CitationStyle) -> Entry {
+	let (name, date) = match style {
+		CitationStyle::Apa => (
+			item.name.map(|name| terminated(&name, '.')),
+			Some(
+				item.release_date
+					.as_ref()
+					.map_or_else(|| String::from("n.d."), apa_date),
+			),
+		),
+		CitationStyle::Ieee => (
+			item.name.map(|name| format!("\u{201c}{}\u{201d}", name)),
+			item.release_date.as_ref().map(ieee_date),
+		),
+	};
+
+	Entry {
+		title: Link::new(name, item.url),
+		subtitle: item.publisher.map(Link::from),
+		date,
+		paragraphs: item.summary.into_iter().collect(),
+		..Default::default()
+	}
+}
+
+impl From<ProjectsItem> for Entry {
+	fn from(item: ProjectsItem) -> Self {
+		let subtitle = text::roles(&item.roles, item.entity);
+
+		Entry {
+			title: Link::new(item.name, item.url),
+			subtitle: subtitle.map(Link::from),
+			date: format_range(item.start_date.as_ref(), item.end_date.as_ref()),
+			location: item.project_type,
+			paragraphs: item.description.into_iter().collect(),
+			highlights: item.highlights,
+			keywords: item.keywords,
+		}
+	}
+}
+
+/// The level indicator is appended to the level as text, e.g. `Advanced ●●●●○`
+fn skill(item: SkillsItem, show_indicator: bool) -> Entry {
+	let level = match (item.level, show_indicator) {
+		(Some(level), true) => match level_score(&level) {
+			Some(score) => Some(format!("{} {}", level, indicator_text(score))),
+			None => {
+				log::warn!("Could not map level {:?} to an indicator. Showing text only", level);
+				Some(level)
+			}
+		},
+		(level, _) => level,
+	};
+
+	Entry {
+		title: Link::new(item.name, None),
+		subtitle: level.map(Link::from),
+		keywords: item.keywords,
+		..Default::default()
+	}
+}
+
+impl From<AwardsItem> for Entry {
+	fn from(item: AwardsItem) -> Self {
+		Entry {
+			title: Link::new(item.title, None),
+			subtitle: item.awarder.map(Link::from),
+			date: item.date.as_ref().map(format_date),
+			paragraphs: item.summary.into_iter().collect(),
+			..Default::default()
+		}
+	}
+}
+
+impl From<CertificatesItem> for Entry {
+	fn from(item: CertificatesItem) -> Self {
+		Entry {
+			title: Link::new(item.name, item.url),
+			subtitle: item.issuer.map(Link::from),
+			date: item.date.as_ref().map(format_date),
+			..Default::default()
+		}
+	}
+}
+
+impl From<VolunteerItem> for Entry {
+	fn from(item: VolunteerItem) -> Self {
+		Entry {
+			title: Link::new(item.organization, item.url),
+			subtitle: item.position.map(Link::from),
+			date: format_range(item.start_date.as_ref(), item.end_date.as_ref()),
+			paragraphs: item.summary.into_iter().collect(),
+			highlights: item.highlights,
+			..Default::default()
+		}
+	}
+}
+
+impl From<InterestsItem> for Entry {
+	fn from(item: InterestsItem) -> Self {
+		Entry {
+			title: Link::new(item.name, None),
+			keywords: item.keywords,
+			..Default::default()
+		}
+	}
+}
+
+impl From<LanguagesItem> for Entry {
+	fn from(item: LanguagesItem) -> Self {
+		Entry {
+			title: Link::new(item.language, None),
+			subtitle: item.fluency.map(Link::from),
+			..Default::default()
+		}
+	}
+}
+
+impl From<ReferencesItem> for Entry {
+	fn from(item: ReferencesItem) -> Self {
+		Entry {
+			title: Link::new(item.name, None),
+			paragraphs: item.reference.into_iter().collect(),
+			..Default::default()
+		}
+	}
+}
+
+fn section<T: Into<Entry>>(kind: SectionKind, items: Vec<T>, keep_empty: bool) -> Option<Section> {
+	match items.is_empty() && !keep_empty {
+		true => None,
+		false => Some(Section {
+			kind,
+			entries: items.into_iter().map(Into::into).collect(),
+		}),
+	}
+}
+
+impl Outline {
+	/// Flattens `val` into sections, in the same order as the HTML output. Sections without entries are left out
+	/// unless `opts.all_sections` is set
+	pub fn new(val: Resume, opts: &Options) -> Self {
+		let keep = opts.all_sections;
+		let references = match (opts.references_on_request, val.references.is_empty()) {
+			(true, false) => Some(Section {
+				kind: SectionKind::References,
+				entries: vec![Entry {
+					paragraphs: vec![String::from(text::REFERENCES_ON_REQUEST)],
+					..Default::default()
+				}],
+			}),
+			_ => section(SectionKind::References, val.references, keep),
+		};
+
+		let sections = [
+			section(SectionKind::Education, val.education, keep),
+			section(SectionKind::Work, val.work, keep),
+			section(
+				SectionKind::Publications,
+				val.publications
+					.into_iter()
+					.map(|item| publication(item, opts.citation_style))
+					.collect(),
+				keep,
+			),
+			section(SectionKind::Projects, val.projects, keep),
+			section(
+				SectionKind::Skills,
+				val.skills
+					.into_iter()
+					.map(|item| skill(item, opts.skill_level_indicator))
+					.collect(),
+				keep,
+			),
+			section(SectionKind::Awards, val.awards, keep),
+			section(SectionKind::Certificates, val.certificates, keep),
+			section(SectionKind::Volunteer, val.volunteer, keep),
+			section(SectionKind::Interests, val.interests, keep),
+			section(SectionKind::Languages, val.languages, keep),
+			references,
+		];
+
+		Outline {
+			header: val.basics.into(),
+			sections: sections.into_iter().flatten().collect(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rsb_schema::Iso8601;
+
+	use super::*;
+
+	#[test]
+	fn publication_follows_citation_style() {
+		let item = PublicationsItem {
+			name: Some(String::from("Paper")),
+			release_date: Some(Iso8601::YearMonth(2020, 3)),
+			..Default::default()
+		};
+
+		let apa = publication(item.clone(), CitationStyle::Apa);
+		assert_eq!(apa.title.unwrap().text, "Paper.");
+		assert_eq!(apa.date.as_deref(), Some("2020, Mar"));
+
+		let ieee = publication(item, CitationStyle::Ieee);
+		assert_eq!(ieee.title.unwrap().text, "\u{201c}Paper\u{201d}");
+		assert_eq!(ieee.date.as_deref(), Some("Mar 2020"));
+	}
+
+	#[test]
+	fn skill_level_indicator_is_opt_in() {
+		let item = SkillsItem {
+			level: Some(String::from("3/5")),
+			..Default::default()
+		};
+
+		assert_eq!(
+			skill(item.clone(), false).subtitle.map(|link| link.text).as_deref(),
+			Some("3/5")
+		);
+		assert_eq!(
+			skill(item, true).subtitle.map(|link| link.text).as_deref(),
+			Some("3/5 ●●●○○")
+		);
+	}
+
+	#[test]
+	fn all_sections_keeps_empty_ones() {
+		assert!(Outline::new(Resume::default(), &Options::default()).sections.is_empty());
+
+		let opts = Options {
+			all_sections: true,
+			..Default::default()
+		};
+		let outline = Outline::new(Resume::default(), &opts);
+		assert_eq!(outline.sections.len(), 11);
+		assert!(outline.sections.iter().all(|section| section.entries.is_empty()));
+	}
+}
@@ -0,0 +1,607 @@
+use std::{collections::BTreeSet, io::Cursor};
+
+use chrono::NaiveDate;
+use printpdf::{
+	lopdf::{self, Dictionary, Object, StringFormat},
+	IndirectFontRef, Line, Mm, OffsetDateTime, PdfDocument, PdfDocumentReference, PdfLayerIndex, PdfLayerReference,
+	PdfPageIndex, Point,
+};
+use rsb_schema::Resume;
+use subsetter::Profile;
+use ttf_parser::Face;
+
+use crate::{
+	dates,
+	outline::{Entry, Header, Outline, Section},
+	Options,
+};
+
+const REGULAR_FONT: &[u8] = include_bytes!("../fonts/DejaVuSerif.ttf");
+const BOLD_FONT: &[u8] = include_bytes!("../fonts/DejaVuSerif-Bold.ttf");
+const ITALIC_FONT: &[u8] = include_bytes!("../fonts/DejaVuSerif-Italic.ttf");
+
+// A4, in mm
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 18.0;
+const CONTENT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+const PT_TO_MM: f32 = 25.4 / 72.0;
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+const LINE_SPACING: f32 = 1.3;
+const BULLET_INDENT: f32 = 4.0;
+const LAYER_NAME: &str = "Resume";
+
+const NAME_SIZE: f32 = 20.0;
+const HEADING_SIZE: f32 = 12.0;
+const TEXT_SIZE: f32 = 9.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+	Regular,
+	Bold,
+	Italic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+	Left,
+	Center,
+}
+
+/// A piece of text sharing a style and link target
+struct Run {
+	text: String,
+	style: Style,
+	url: Option<String>,
+}
+
+impl Run {
+	fn new(text: impl Into<String>, style: Style) -> Self {
+		Run {
+			text: text.into(),
+			style,
+			url: None,
+		}
+	}
+
+	fn link(text: impl Into<String>, style: Style, url: Option<String>) -> Self {
+		Run {
+			text: text.into(),
+			style,
+			url,
+		}
+	}
+}
+
+/// Wrapped text with optional decorations on its first line
+struct Paragraph {
+	runs: Vec<Run>,
+	size: f32,
+	indent: f32,
+	align: Align,
+	aside: Option<Run>,
+	bullet: bool,
+}
+
+impl Paragraph {
+	fn new(runs: Vec<Run>) -> Self {
+		Paragraph {
+			runs,
+			size: TEXT_SIZE,
+			indent: 0.0,
+			align: Align::Left,
+			aside: None,
+			bullet: false,
+		}
+	}
+
+	fn text(text: impl Into<String>, style: Style) -> Self {
+		Paragraph::new(vec![Run::new(text, style)])
+	}
+
+	fn size(mut self, size: f32) -> Self {
+		self.size = size;
+		self
+	}
+
+	fn centered(mut self) -> Self {
+		self.align = Align::Center;
+		self
+	}
+
+	/// Right-aligned text on the first line, e.g. a date
+	fn aside(mut self, aside: Option<Run>) -> Self {
+		self.aside = aside;
+		self
+	}
+
+	fn bullet(mut self) -> Self {
+		self.indent = BULLET_INDENT;
+		self.bullet = true;
+		self
+	}
+}
+
+struct Font {
+	data: &'static [u8],
+	face: Face<'static>,
+}
+
+impl Font {
+	fn parse(data: &'static [u8]) -> anyhow::Result<Self> {
+		Ok(Font {
+			data,
+			face: Face::parse(data, 0)?,
+		})
+	}
+
+	/// The font cut down to the glyphs for `chars`. Glyph IDs stay the same, so widths measured on the full face
+	/// still hold
+	fn subset(&self, chars: &BTreeSet<char>) -> anyhow::Result<Vec<u8>> {
+		// glyph 0 is the fallback for missing characters
+		let glyphs: Vec<u16> = std::iter::once(0)
+			.chain(
+				chars
+					.iter()
+					.filter_map(|c| self.face.glyph_index(*c))
+					.map(|glyph| glyph.0),
+			)
+			.collect();
+		Ok(subsetter::subset(self.data, 0, Profile::pdf(&glyphs))?)
+	}
+
+	fn embed(&self, doc: &PdfDocumentReference, chars: &BTreeSet<char>) -> anyhow::Result<IndirectFontRef> {
+		Ok(doc.add_external_font(Cursor::new(self.subset(chars)?))?)
+	}
+
+	/// Width of `text` in mm when set at `size` pt
+	fn width(&self, text: &str, size: f32) -> f32 {
+		let units_per_em = self.face.units_per_em() as f32;
+		let units: f32 = text
+			.chars()
+			.map(|c| {
+				self.face
+					.glyph_index(c)
+					.and_then(|glyph| self.face.glyph_hor_advance(glyph))
+					.map(f32::from)
+					.unwrap_or(units_per_em / 2.0)
+			})
+			.sum();
+
+		units / units_per_em * size * PT_TO_MM
+	}
+}
+
+/// Text placed during layout. It is written once layout is done and the characters each font needs are known
+struct Text {
+	page: PdfPageIndex,
+	layer: PdfLayerIndex,
+	text: String,
+	style: Style,
+	size: f32,
+	x: f32,
+	y: f32,
+}
+
+/// A clickable area, in pt from the bottom left of its page
+struct Link {
+	page: usize,
+	rect: [f32; 4],
+	url: String,
+}
+
+/// A word placed on a line, before it is written to the page
+struct Word<'a> {
+	text: &'a str,
+	style: Style,
+	url: Option<&'a str>,
+	width: f32,
+}
+
+struct Writer {
+	doc: PdfDocumentReference,
+	page: PdfPageIndex,
+	// zero based, `PdfPageIndex` does not expose it
+	page_number: usize,
+	layer: PdfLayerIndex,
+	regular: Font,
+	bold: Font,
+	italic: Font,
+	texts: Vec<Text>,
+	links: Vec<Link>,
+	// baseline cursor, in mm from the bottom of the page
+	y: f32,
+}
+
+impl Writer {
+	fn new(title: &str) -> anyhow::Result<Self> {
+		let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), LAYER_NAME);
+
+		Ok(Writer {
+			regular: Font::parse(REGULAR_FONT)?,
+			bold: Font::parse(BOLD_FONT)?,
+			italic: Font::parse(ITALIC_FONT)?,
+			doc,
+			page,
+			page_number: 0,
+			layer,
+			texts: Vec::new(),
+			links: Vec::new(),
+			y: PAGE_HEIGHT - MARGIN,
+		})
+	}
+
+	fn font(&self, style: Style) -> &Font {
+		match style {
+			Style::Regular => &self.regular,
+			Style::Bold => &self.bold,
+			Style::Italic => &self.italic,
+		}
+	}
+
+	fn current_layer(&self) -> PdfLayerReference {
+		self.doc.get_page(self.page).get_layer(self.layer)
+	}
+
+	/// Starts a new page unless `height` mm still fit above the bottom margin
+	fn ensure_space(&mut self, height: f32) {
+		if self.y - height >= MARGIN {
+			return;
+		}
+
+		let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), LAYER_NAME);
+		self.page = page;
+		self.page_number += 1;
+		self.layer = layer;
+		self.y = PAGE_HEIGHT - MARGIN;
+	}
+
+	fn skip(&mut self, height: f32) {
+		self.y -= height;
+	}
+
+	fn write(&mut self, text: &str, style: Style, size: f32, x: f32, url: Option<&str>) {
+		self.texts.push(Text {
+			page: self.page,
+			layer: self.layer,
+			text: text.to_string(),
+			style,
+			size,
+			x,
+			y: self.y,
+		});
+
+		if let Some(url) = url {
+			let width = self.font(style).width(text, size);
+			let rect = [
+				x,
+				self.y - 0.25 * size * PT_TO_MM,
+				x + width,
+				self.y + 0.8 * size * PT_TO_MM,
+			];
+			self.links.push(Link {
+				page: self.page_number,
+				rect: rect.map(|mm| mm / PT_TO_MM),
+				url: url.to_string(),
+			});
+		}
+	}
+
+	fn rule(&self) {
+		let layer = self.current_layer();
+		layer.set_outline_thickness(0.5);
+		layer.add_line(Line {
+			points: vec![
+				(Point::new(Mm(MARGIN), Mm(self.y)), false),
+				(Point::new(Mm(PAGE_WIDTH - MARGIN), Mm(self.y)), false),
+			],
+			is_closed: false,
+		});
+	}
+
+	fn break_lines<'a>(&self, runs: &'a [Run], size: f32, width: f32) -> Vec<Vec<Word<'a>>> {
+		let mut lines: Vec<Vec<Word>> = vec![Vec::new()];
+		let mut line_width = 0.0;
+
+		for run in runs {
+			let font = self.font(run.style);
+			let space = font.width(" ", size);
+
+			for text in run.text.split_whitespace() {
+				let word = Word {
+					text,
+					style: run.style,
+					url: run.url.as_deref(),
+					width: font.width(text, size),
+				};
+
+				let line = lines.last_mut().expect("there is always a line");
+				if line.is_empty() {
+					line_width = word.width;
+					line.push(word);
+				} else if line_width + space + word.width <= width {
+					line_width += space + word.width;
+					line.push(word);
+				} else {
+					line_width = word.width;
+					lines.push(vec![word]);
+				}
+			}
+		}
+
+		lines.retain(|line| !line.is_empty());
+		lines
+	}
+
+	fn paragraph(&mut self, paragraph: Paragraph) {
+		let size = paragraph.size;
+		let x = MARGIN + paragraph.indent;
+		let width = CONTENT_WIDTH - paragraph.indent;
+		let line_height = size * LINE_SPACING * PT_TO_MM;
+		let aside_width = paragraph
+			.aside
+			.as_ref()
+			.map_or(0.0, |run| self.font(run.style).width(&run.text, size) + 2.0);
+
+		let mut lines = self.break_lines(&paragraph.runs, size, width - aside_width);
+		if lines.is_empty() {
+			if paragraph.aside.is_none() {
+				return;
+			}
+			lines.push(Vec::new());
+		}
+
+		for (i, line) in lines.iter().enumerate() {
+			self.ensure_space(line_height);
+			self.skip(line_height);
+
+			let spaces: f32 = line.iter().skip(1).map(|w| self.font(w.style).width(" ", size)).sum();
+			let total = line.iter().map(|w| w.width).sum::<f32>() + spaces;
+			let mut cursor = match paragraph.align {
+				Align::Left => x,
+				Align::Center => x + (width - total) / 2.0,
+			};
+
+			// group neighbouring words with the same style and link into one text object
+			let mut start = 0;
+			while start < line.len() {
+				let mut end = start + 1;
+				while end < line.len() && line[end].style == line[start].style && line[end].url == line[start].url {
+					end += 1;
+				}
+
+				let text = line[start..end].iter().map(|w| w.text).collect::<Vec<_>>().join(" ");
+				let font = self.font(line[start].style);
+				let advance = font.width(&text, size) + font.width(" ", size);
+				self.write(&text, line[start].style, size, cursor, line[start].url);
+				cursor += advance;
+				start = end;
+			}
+
+			if i != 0 {
+				continue;
+			}
+			if paragraph.bullet {
+				self.write("\u{2022}", Style::Regular, size, MARGIN + 1.0, None);
+			}
+			if let Some(aside) = &paragraph.aside {
+				let aside_x = x + width - self.font(aside.style).width(&aside.text, size);
+				self.write(&aside.text, aside.style, size, aside_x, aside.url.as_deref());
+			}
+		}
+	}
+
+	/// Writes the placed text with fonts cut down to the characters it uses. Faces nothing uses are left out
+	fn write_text(&self) -> anyhow::Result<()> {
+		let mut fonts = Vec::new();
+		for style in [Style::Regular, Style::Bold, Style::Italic] {
+			let chars: BTreeSet<char> = self
+				.texts
+				.iter()
+				.filter(|text| text.style == style)
+				.flat_map(|text| text.text.chars())
+				.collect();
+			if !chars.is_empty() {
+				fonts.push((style, self.font(style).embed(&self.doc, &chars)?));
+			}
+		}
+
+		for text in &self.texts {
+			let Some((_, font)) = fonts.iter().find(|(style, _)| *style == text.style) else {
+				continue;
+			};
+			self.doc.get_page(text.page).get_layer(text.layer).use_text(
+				&text.text,
+				text.size,
+				Mm(text.x),
+				Mm(text.y),
+				font,
+			);
+		}
+		Ok(())
+	}
+
+	fn header(&mut self, header: Header) {
+		if let Some(name) = header.name {
+			self.paragraph(Paragraph::text(name, Style::Bold).size(NAME_SIZE).centered());
+		}
+		if let Some(label) = header.label {
+			self.paragraph(Paragraph::text(label, Style::Italic).size(TEXT_SIZE + 1.0).centered());
+		}
+
+		let mut contacts = Vec::new();
+		let links = header
+			.contacts
+			.into_iter()
+			.map(|c| Run::link(c.text, Style::Regular, c.url));
+		for contact in links.chain(header.location.map(|l| Run::new(l, Style::Regular))) {
+			if !contacts.is_empty() {
+				contacts.push(Run::new("|", Style::Regular));
+			}
+			contacts.push(contact);
+		}
+		self.paragraph(Paragraph::new(contacts).centered());
+
+		if let Some(summary) = header.summary {
+			self.skip(1.5);
+			self.paragraph(Paragraph::text(summary, Style::Regular));
+		}
+	}
+
+	fn entry(&mut self, entry: Entry) {
+		self.skip(1.5);
+
+		let title = entry.title.map(|t| Run::link(t.text, Style::Bold, t.url));
+		let date = entry.date.map(|d| Run::new(d, Style::Bold));
+		self.paragraph(Paragraph::new(title.into_iter().collect()).aside(date));
+
+		let subtitle = entry.subtitle.map(|s| Run::link(s.text, Style::Italic, s.url));
+		let location = entry.location.map(|l| Run::new(l, Style::Regular));
+		self.paragraph(Paragraph::new(subtitle.into_iter().collect()).aside(location));
+
+		for paragraph in entry.paragraphs {
+			self.paragraph(Paragraph::text(paragraph, Style::Regular));
+		}
+		for highlight in entry.highlights {
+			self.paragraph(Paragraph::text(highlight, Style::Regular).bullet());
+		}
+		if !entry.keywords.is_empty() {
+			self.paragraph(Paragraph::new(vec![
+				Run::new("Keywords:", Style::Italic),
+				Run::new(entry.keywords.join(", "), Style::Regular),
+			]));
+		}
+	}
+
+	fn section(&mut self, section: Section) {
+		let heading_height = HEADING_SIZE * LINE_SPACING * PT_TO_MM;
+		// keep the heading together with at least a couple of lines of its first entry
+		self.ensure_space(heading_height + 3.0 * TEXT_SIZE * LINE_SPACING * PT_TO_MM);
+		self.skip(3.0);
+		self.paragraph(Paragraph::text(section.kind.title(), Style::Bold).size(HEADING_SIZE));
+		self.skip(1.0);
+		self.rule();
+
+		for entry in section.entries {
+			self.entry(entry);
+		}
+	}
+}
+
+/// FNV-1a over the input, so the same resume and options always get the same document ID
+fn document_id(val: &Resume, opts: &Options) -> anyhow::Result<String> {
+	let mut input = serde_json::to_vec(val)?;
+	input.extend(format!("{:?}", opts).bytes());
+	let hash = input.iter().fold(FNV_OFFSET, |hash, byte| {
+		(hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+	});
+	Ok(format!("{:016X}", hash))
+}
+
+/// Midnight UTC of the document date, or the Unix epoch when there is none. Never the wall clock
+fn pdf_date(date: Option<NaiveDate>) -> anyhow::Result<OffsetDateTime> {
+	let timestamp = date.map_or(0, |date| date.and_time(Default::default()).and_utc().timestamp());
+	Ok(OffsetDateTime::from_unix_timestamp(timestamp)?)
+}
+
+/// printpdf keeps link annotations in a hash map and draws the second half of the trailer ID at random, so both
+/// are written here instead, in page order and from the input
+fn finish(pdf: Vec<u8>, links: Vec<Link>, id: &str) -> anyhow::Result<Vec<u8>> {
+	let mut doc = lopdf::Document::load_mem(&pdf)?;
+	let pages: Vec<lopdf::ObjectId> = doc.get_pages().into_values().collect();
+
+	let mut annotations: Vec<Vec<Object>> = vec![Vec::new(); pages.len()];
+	for link in links {
+		let mut action = Dictionary::new();
+		action.set("S", Object::Name(b"URI".to_vec()));
+		action.set("URI", Object::String(link.url.into_bytes(), StringFormat::Literal));
+
+		let mut annotation = Dictionary::new();
+		annotation.set("Type", Object::Name(b"Annot".to_vec()));
+		annotation.set("Subtype", Object::Name(b"Link".to_vec()));
+		annotation.set("Rect", link.rect.map(Object::Real).to_vec());
+		annotation.set("Border", vec![0.into(), 0.into(), 0.into()]);
+		annotation.set("A", action);
+		annotations[link.page].push(doc.add_object(annotation).into());
+	}
+	for (page, annotations) in pages.into_iter().zip(annotations) {
+		doc.get_object_mut(page)?.as_dict_mut()?.set("Annots", annotations);
+	}
+
+	let id = Object::String(id.as_bytes().to_vec(), StringFormat::Literal);
+	doc.trailer.set("ID", vec![id.clone(), id]);
+
+	// printpdf leaves font files uncompressed, even in release builds
+	doc.compress();
+	let mut res = Vec::new();
+	doc.save_to(&mut res)?;
+	Ok(res)
+}
+
+/// Lays `val` out into a paginated A4 PDF with subsetted embedded fonts
+pub fn generate(val: Resume, opts: Options) -> anyhow::Result<Vec<u8>> {
+	let id = document_id(&val, &opts)?;
+	let date = pdf_date(dates::document_date(opts.date, val.meta.last_modified.as_deref()))?;
+	let name = val.basics.name.clone();
+	let label = val.basics.label.clone();
+	let outline = Outline::new(val, &opts);
+
+	let title = name.clone().unwrap_or_else(|| String::from("Resume"));
+	let mut writer = Writer::new(&title)?;
+	writer.header(outline.header);
+	for section in outline.sections {
+		writer.section(section);
+	}
+	writer.write_text()?;
+
+	let mut doc = writer
+		.doc
+		.with_title(title)
+		.with_creator("rsb")
+		.with_producer("rsb")
+		.with_creation_date(date)
+		.with_mod_date(date)
+		.with_metadata_date(date)
+		.with_document_id(id.clone());
+	if let Some(name) = name {
+		doc = doc.with_author(name);
+	}
+	if let Some(label) = label {
+		doc = doc.with_subject(label);
+	}
+
+	finish(doc.save_to_bytes()?, writer.links, &id)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn subset_keeps_only_used_outlines() {
+		let font = Font::parse(REGULAR_FONT).unwrap();
+		let data = font.subset(&"Ab".chars().collect()).unwrap();
+		assert!(data.len() < REGULAR_FONT.len() / 4);
+
+		let face = Face::parse(&data, 0).unwrap();
+		let glyph = |c| face.glyph_index(c).unwrap();
+		assert!(face.glyph_bounding_box(glyph('A')).is_some());
+		assert!(face.glyph_bounding_box(glyph('Z')).is_none());
+		assert_eq!(
+			face.glyph_hor_advance(glyph('A')),
+			font.face.glyph_hor_advance(glyph('A'))
+		);
+	}
+
+	#[test]
+	fn document_id_depends_on_input_only() {
+		let mut val = Resume::default();
+		let opts = Options::default();
+		let id = document_id(&val, &opts).unwrap();
+		assert_eq!(id, document_id(&val, &opts).unwrap());
+
+		val.basics.name = Some(String::from("Jane Doe"));
+		assert_ne!(id, document_id(&val, &opts).unwrap());
+	}
+}
@@ -0,0 +1,206 @@
+use std::fmt::Write;
+
+use rsb_schema::Resume;
+
+use crate::{
+	outline::{Entry, Header, Link, Outline, Section, SectionKind},
+	Options,
+};
+
+const TEXT_WIDTH: usize = 80;
+const BULLET: &str = "- ";
+
+/// Section order most applicant tracking systems expect
+const SECTION_ORDER: [SectionKind; 11] = [
+	SectionKind::Work,
+	SectionKind::Education,
+	SectionKind::Skills,
+	SectionKind::Certificates,
+	SectionKind::Projects,
+	SectionKind::Publications,
+	SectionKind::Awards,
+	SectionKind::Volunteer,
+	SectionKind::Languages,
+	SectionKind::Interests,
+	SectionKind::References,
+];
+
+fn heading(kind: SectionKind) -> &'static str {
+	match kind {
+		SectionKind::Work => "WORK EXPERIENCE",
+		SectionKind::Education => "EDUCATION",
+		SectionKind::Skills => "SKILLS",
+		SectionKind::Certificates => "CERTIFICATIONS",
+		SectionKind::Projects => "PROJECTS",
+		SectionKind::Publications => "PUBLICATIONS",
+		SectionKind::Awards => "AWARDS",
+		SectionKind::Volunteer => "VOLUNTEER EXPERIENCE",
+		SectionKind::Languages => "LANGUAGES",
+		SectionKind::Interests => "INTERESTS",
+		SectionKind::References => "REFERENCES",
+	}
+}
+
+/// Greedy word wrap to [`TEXT_WIDTH`], with `prefix` on the first line and matching indentation after
+fn wrap(out: &mut String, text: &str, prefix: &str) -> std::fmt::Result {
+	let indent = " ".repeat(prefix.chars().count());
+	let mut line = String::from(prefix);
+	let mut line_len = prefix.chars().count();
+	let mut empty = true;
+
+	for word in text.split_whitespace() {
+		let word_len = word.chars().count();
+		if !empty && line_len + 1 + word_len > TEXT_WIDTH {
+			writeln!(out, "{}", line)?;
+			line = indent.clone();
+			line_len = indent.len();
+			empty = true;
+		}
+		if !empty {
+			line.push(' ');
+			line_len += 1;
+		}
+		line.push_str(word);
+		line_len += word_len;
+		empty = false;
+	}
+
+	if !empty {
+		writeln!(out, "{}", line)?;
+	}
+	Ok(())
+}
+
+fn link(link: &Link) -> String {
+	match &link.url {
+		Some(url) if url != &link.text && !url.starts_with("mailto:") => format!("{} ({})", link.text, url),
+		_ => link.text.clone(),
+	}
+}
+
+fn header(out: &mut String, header: Header) -> std::fmt::Result {
+	if let Some(name) = header.name {
+		wrap(out, &name, "")?;
+	}
+	if let Some(label) = header.label {
+		wrap(out, &label, "")?;
+	}
+	for contact in &header.contacts {
+		wrap(out, &link(contact), "")?;
+	}
+	if let Some(location) = header.location {
+		wrap(out, &location, "")?;
+	}
+
+	if let Some(summary) = header.summary {
+		writeln!(out, "\nSUMMARY\n")?;
+		wrap(out, &summary, "")?;
+	}
+	Ok(())
+}
+
+fn entry(out: &mut String, entry: Entry) -> std::fmt::Result {
+	if let Some(title) = &entry.title {
+		wrap(out, &link(title), "")?;
+	}
+
+	let details: Vec<String> = entry
+		.subtitle
+		.as_ref()
+		.map(link)
+		.into_iter()
+		.chain(entry.location)
+		.collect();
+	if !details.is_empty() {
+		wrap(out, &details.join(" | "), "")?;
+	}
+	if let Some(date) = entry.date {
+		wrap(out, &date, "")?;
+	}
+
+	for paragraph in entry.paragraphs {
+		wrap(out, &paragraph, "")?;
+	}
+	for highlight in entry.highlights {
+		wrap(out, &highlight, BULLET)?;
+	}
+	if !entry.keywords.is_empty() {
+		wrap(out, &entry.keywords.join(", "), "Keywords: ")?;
+	}
+	Ok(())
+}
+
+fn section(out: &mut String, section: Section) -> std::fmt::Result {
+	writeln!(out, "\n{}", heading(section.kind))?;
+	for item in section.entries {
+		writeln!(out)?;
+		entry(out, item)?;
+	}
+	Ok(())
+}
+
+/// Renders `val` as plain text that applicant tracking systems can parse
+pub fn generate(val: Resume, opts: Options) -> anyhow::Result<String> {
+	let mut outline = Outline::new(val, &opts);
+	outline
+		.sections
+		.sort_by_key(|s| SECTION_ORDER.iter().position(|kind| *kind == s.kind));
+
+	let mut out = String::new();
+	header(&mut out, outline.header)?;
+	for item in outline.sections {
+		section(&mut out, item)?;
+	}
+	Ok(out.trim_start_matches('\n').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use rsb_schema::{EducationItem, WorkItem};
+
+	use super::*;
+
+	#[test]
+	fn wrap_respects_text_width() {
+		let mut out = String::new();
+		wrap(&mut out, &"word ".repeat(40), "").unwrap();
+
+		let lines: Vec<&str> = out.lines().collect();
+		assert_eq!(lines.len(), 3);
+		assert!(lines.iter().all(|line| line.chars().count() <= TEXT_WIDTH));
+		assert_eq!(lines[0].chars().count(), 79);
+	}
+
+	#[test]
+	fn bullet_continuation_is_indented() {
+		let mut out = String::new();
+		wrap(&mut out, &"highlight ".repeat(12), BULLET).unwrap();
+
+		let lines: Vec<&str> = out.lines().collect();
+		assert_eq!(lines.len(), 2);
+		assert!(lines[0].starts_with("- highlight"));
+		assert!(lines[1].starts_with("  highlight"));
+		assert!(lines.iter().all(|line| line.chars().count() <= TEXT_WIDTH));
+	}
+
+	#[test]
+	fn work_comes_before_education() {
+		let resume = Resume {
+			education: vec![EducationItem {
+				institution: Some(String::from("MIT")),
+				..Default::default()
+			}],
+			work: vec![WorkItem {
+				name: Some(String::from("Acme")),
+				..Default::default()
+			}],
+			..Default::default()
+		};
+
+		let out = generate(resume, Options::default()).unwrap();
+		let work = out.find("WORK EXPERIENCE").unwrap();
+		let education = out.find("EDUCATION").unwrap();
+		assert!(work < education);
+		assert!(out.find("Acme").unwrap() < education);
+	}
+}
@@ -0,0 +1,28 @@
+//! Wording shared by the HTML components and the outline, so every format says the same thing
+
+pub const REFERENCES_ON_REQUEST: &str = "References available on request.";
+
+/// `Bachelor in Physics`, or whichever of the two is known
+pub fn degree(study_type: Option<String>, area: Option<String>) -> Option<String> {
+	match (study_type, area) {
+		(Some(study_type), Some(area)) => Some(format!("{} in {}", study_type, area)),
+		(study_type, area) => study_type.or(area),
+	}
+}
+
+/// `GitHub: jdoe`, or whichever of the two is known
+pub fn profile(network: Option<String>, username: Option<String>) -> Option<String> {
+	match (network, username) {
+		(Some(network), Some(username)) => Some(format!("{}: {}", network, username)),
+		(network, username) => username.or(network),
+	}
+}
+
+/// `Lead, Maintainer at Acme`, the roles alone, or the entity alone
+pub fn roles(roles: &[String], entity: Option<String>) -> Option<String> {
+	match (roles.is_empty(), entity) {
+		(false, Some(entity)) => Some(format!("{} at {}", roles.join(", "), entity)),
+		(false, None) => Some(roles.join(", ")),
+		(true, entity) => entity,
+	}
+}
@@ -0,0 +1,190 @@
+use std::fmt::Write;
+
+use rsb_schema::Resume;
+
+use crate::{
+	outline::{Entry, Header, Link, Outline, Section},
+	Options,
+};
+
+/// Bundled layout. User content is only ever passed to these functions as string literals
+const LAYOUT: &str = r#"#set page(paper: "a4", margin: 18mm)
+#set text(size: 10pt)
+#set par(justify: false)
+
+#let resume-header(name: none, label: none, contacts: (), summary: none) = {
+	align(center)[
+		#if name != none { text(size: 20pt, weight: "bold", name) }
+		#if label != none { [\ #emph(label)] }
+		#if contacts.len() > 0 { [\ #contacts.join([ | ])] }
+	]
+	if summary != none { par(summary) }
+}
+
+#let resume-section(title) = {
+	v(8pt)
+	text(size: 12pt, weight: "bold", title)
+	v(-6pt)
+	line(length: 100%, stroke: 0.5pt)
+}
+
+#let resume-entry(
+	title: none,
+	date: none,
+	subtitle: none,
+	location: none,
+	paragraphs: (),
+	highlights: (),
+	keywords: (),
+) = {
+	v(4pt)
+	if title != none or date != none {
+		grid(
+			columns: (1fr, auto),
+			if title != none { strong(title) },
+			if date != none { strong(date) },
+		)
+	}
+	if subtitle != none or location != none {
+		grid(
+			columns: (1fr, auto),
+			if subtitle != none { emph(subtitle) },
+			if location != none { location },
+		)
+	}
+	for paragraph in paragraphs { par(paragraph) }
+	if highlights.len() > 0 { list(..highlights) }
+	if keywords.len() > 0 { [#emph[Keywords:] #keywords.join(", ")] }
+}
+"#;
+
+/// Quotes `text` as a Typst string literal
+fn string(text: &str) -> String {
+	let mut res = String::with_capacity(text.len() + 2);
+	res.push('"');
+	for c in text.chars() {
+		match c {
+			'\\' => res.push_str(r"\\"),
+			'"' => res.push_str("\\\""),
+			'\n' => res.push_str(r"\n"),
+			'\r' => res.push_str(r"\r"),
+			'\t' => res.push_str(r"\t"),
+			_ => res.push(c),
+		}
+	}
+	res.push('"');
+	res
+}
+
+fn opt(text: Option<&str>) -> String {
+	text.map(string).unwrap_or_else(|| String::from("none"))
+}
+
+/// A Typst array literal. The trailing comma keeps single element arrays from becoming parenthesized values
+fn array(items: impl IntoIterator<Item = String>) -> String {
+	let items: Vec<String> = items.into_iter().collect();
+	match items.is_empty() {
+		true => String::from("()"),
+		false => format!("({},)", items.join(", ")),
+	}
+}
+
+fn link(link: &Link) -> String {
+	match &link.url {
+		Some(url) => format!("link({}, {})", string(url), string(&link.text)),
+		None => string(&link.text),
+	}
+}
+
+fn header(out: &mut String, header: Header) -> std::fmt::Result {
+	let contacts = header
+		.contacts
+		.iter()
+		.map(link)
+		.chain(header.location.as_deref().map(string));
+
+	writeln!(
+		out,
+		"#resume-header(name: {}, label: {}, contacts: {}, summary: {})",
+		opt(header.name.as_deref()),
+		opt(header.label.as_deref()),
+		array(contacts),
+		opt(header.summary.as_deref()),
+	)
+}
+
+fn entry(out: &mut String, entry: Entry) -> std::fmt::Result {
+	writeln!(out, "#resume-entry(")?;
+	writeln!(
+		out,
+		"\ttitle: {},",
+		entry.title.as_ref().map(link).unwrap_or_else(|| String::from("none"))
+	)?;
+	writeln!(out, "\tdate: {},", opt(entry.date.as_deref()))?;
+	writeln!(
+		out,
+		"\tsubtitle: {},",
+		entry
+			.subtitle
+			.as_ref()
+			.map(link)
+			.unwrap_or_else(|| String::from("none"))
+	)?;
+	writeln!(out, "\tlocation: {},", opt(entry.location.as_deref()))?;
+	writeln!(
+		out,
+		"\tparagraphs: {},",
+		array(entry.paragraphs.iter().map(|p| string(p)))
+	)?;
+	writeln!(
+		out,
+		"\thighlights: {},",
+		array(entry.highlights.iter().map(|h| string(h)))
+	)?;
+	writeln!(out, "\tkeywords: {},", array(entry.keywords.iter().map(|k| string(k))))?;
+	writeln!(out, ")")
+}
+
+fn section(out: &mut String, section: Section) -> std::fmt::Result {
+	writeln!(out, "\n#resume-section({})", string(section.kind.title()))?;
+	for item in section.entries {
+		entry(out, item)?;
+	}
+	Ok(())
+}
+
+/// Renders `val` into a self-contained `.typ` document
+pub fn generate(val: Resume, opts: Options) -> anyhow::Result<String> {
+	let outline = Outline::new(val, &opts);
+	let mut out = String::new();
+
+	match outline.header.name.as_deref() {
+		Some(name) => writeln!(out, "#set document(title: {0}, author: {0})", string(name))?,
+		None => writeln!(out, "#set document(title: \"Resume\")")?,
+	}
+	writeln!(out, "{}", LAYOUT)?;
+
+	header(&mut out, outline.header)?;
+	for item in outline.sections {
+		section(&mut out, item)?;
+	}
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn string_escapes_quotes_and_control_characters() {
+		assert_eq!(string(r#"say "hi" \ #set"#), r#""say \"hi\" \\ #set""#);
+		assert_eq!(string("a\nb\r\tc"), r#""a\nb\r\tc""#);
+		assert_eq!(opt(None), "none");
+	}
+
+	#[test]
+	fn array_keeps_single_elements_an_array() {
+		assert_eq!(array(Vec::new()), "()");
+		assert_eq!(array([string("a")]), r#"("a",)"#);
+	}
+}
@@ -0,0 +1,161 @@
+use std::fmt::Write;
+
+use rsb_schema::Basics;
+
+/// Content lines are folded after this many octets, as RFC 6350 recommends
+const LINE_LENGTH: usize = 75;
+
+/// Escapes a text value, including the component separators of structured values like `N` and `ADR`
+fn escape(text: &str) -> String {
+	let mut res = String::with_capacity(text.len());
+	for c in text.chars() {
+		match c {
+			'\\' => res.push_str(r"\\"),
+			',' => res.push_str(r"\,"),
+			';' => res.push_str(r"\;"),
+			'\n' => res.push_str(r"\n"),
+			'\r' => {}
+			_ => res.push(c),
+		}
+	}
+	res
+}
+
+/// Parameter values can not be escaped, so they are quoted and stripped of what a quoted value can not hold
+fn param(value: &str) -> String {
+	let value: String = value.chars().filter(|c| !matches!(c, '"' | '\r' | '\n')).collect();
+	format!("\"{}\"", value)
+}
+
+/// Writes one content line, folded without splitting a UTF-8 sequence
+fn line(out: &mut String, content: &str) -> std::fmt::Result {
+	let mut len = 0;
+	for c in content.chars() {
+		if len + c.len_utf8() > LINE_LENGTH {
+			out.push_str("\r\n ");
+			// the leading space of a continuation line counts towards its length
+			len = 1;
+		}
+		out.push(c);
+		len += c.len_utf8();
+	}
+	write!(out, "\r\n")
+}
+
+/// Given and family names of a full name. Basics only has the full name, so the last word is taken as the family name
+pub fn split_name(name: &str) -> (&str, &str) {
+	let name = name.trim();
+	match name.rsplit_once(char::is_whitespace) {
+		Some((given, family)) => (given.trim(), family),
+		None => ("", name),
+	}
+}
+
+/// `N` wants family and given names apart
+fn structured_name(name: &str) -> String {
+	let (given, family) = split_name(name);
+	format!("N:{};{};;;", escape(family), escape(given))
+}
+
+/// Renders the contact details in `basics` as a vCard 4.0
+pub fn generate(basics: Basics) -> anyhow::Result<String> {
+	let mut out = String::new();
+	line(&mut out, "BEGIN:VCARD")?;
+	line(&mut out, "VERSION:4.0")?;
+
+	// FN is the only property a vCard must have
+	let name = basics.name.unwrap_or_default();
+	line(&mut out, &format!("FN:{}", escape(&name)))?;
+	if !name.trim().is_empty() {
+		line(&mut out, &structured_name(&name))?;
+	}
+
+	if let Some(label) = basics.label {
+		line(&mut out, &format!("TITLE:{}", escape(&label)))?;
+	}
+	if let Some(email) = basics.email {
+		line(&mut out, &format!("EMAIL:{}", escape(&email)))?;
+	}
+	if let Some(phone) = basics.phone {
+		line(&mut out, &format!("TEL;VALUE=text:{}", escape(&phone)))?;
+	}
+	if let Some(url) = basics.url {
+		line(&mut out, &format!("URL:{}", url))?;
+	}
+	if let Some(image) = basics.image {
+		line(&mut out, &format!("PHOTO:{}", image))?;
+	}
+
+	let location = basics.location;
+	let parts = [
+		location.address,
+		location.city,
+		location.region,
+		location.postal_code,
+		location.country_code,
+	];
+	if parts.iter().flatten().any(|part| !part.trim().is_empty()) {
+		let [street, locality, region, code, country] = parts.map(|part| escape(part.as_deref().unwrap_or_default()));
+		line(
+			&mut out,
+			&format!("ADR:;;{};{};{};{};{}", street, locality, region, code, country),
+		)?;
+	}
+
+	// RFC 9554 social profiles. A profile without a URL is kept as its username
+	for profile in basics.profiles {
+		let service = profile
+			.network
+			.as_deref()
+			.map(|network| format!(";SERVICE-TYPE={}", param(network)))
+			.unwrap_or_default();
+		match (profile.url, profile.username) {
+			(Some(url), _) => line(&mut out, &format!("SOCIALPROFILE{}:{}", service, url))?,
+			(None, Some(username)) => line(
+				&mut out,
+				&format!("SOCIALPROFILE{};VALUE=text:{}", service, escape(&username)),
+			)?,
+			(None, None) => log::warn!("profile has no url or username. Skipping"),
+		}
+	}
+
+	line(&mut out, "END:VCARD")?;
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn split_name_takes_last_word_as_family_name() {
+		assert_eq!(split_name(" Mary Ann  Smith "), ("Mary Ann", "Smith"));
+		assert_eq!(split_name("Prince"), ("", "Prince"));
+		assert_eq!(structured_name("Jane Doe"), "N:Doe;Jane;;;");
+	}
+
+	#[test]
+	fn escape_text_values() {
+		assert_eq!(escape("a,b;c\\d\r\ne"), r"a\,b\;c\\d\ne");
+		assert_eq!(param("Home \"office\"\n"), "\"Home office\"");
+	}
+
+	#[test]
+	fn line_folds_at_75_octets() {
+		let mut out = String::new();
+		line(&mut out, &"a".repeat(160)).unwrap();
+		let lines: Vec<&str> = out.split("\r\n").collect();
+		assert_eq!(lines.iter().map(|line| line.len()).collect::<Vec<_>>(), [75, 75, 12, 0]);
+		assert!(lines[1].starts_with(' ') && lines[2].starts_with(' '));
+	}
+
+	#[test]
+	fn line_keeps_multibyte_characters_whole() {
+		let mut out = String::new();
+		// 37 two-octet characters fill 74 octets, so the 38th moves to the next line
+		line(&mut out, &"é".repeat(40)).unwrap();
+		let lines: Vec<&str> = out.split("\r\n").collect();
+		assert_eq!(lines[0], "é".repeat(37));
+		assert_eq!(lines[1], format!(" {}", "é".repeat(3)));
+	}
+}
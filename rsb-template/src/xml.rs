@@ -0,0 +1,66 @@
+use rsb_schema::Basics;
+
+/// Escapes text for XML content and attribute values, dropping control characters XML 1.0 does not allow
+pub fn escape(text: &str) -> String {
+	let mut res = String::with_capacity(text.len());
+	for c in text.chars() {
+		match c {
+			'&' => res.push_str("&amp;"),
+			'<' => res.push_str("&lt;"),
+			'>' => res.push_str("&gt;"),
+			'"' => res.push_str("&quot;"),
+			'\'' => res.push_str("&apos;"),
+			'\t' | '\n' | '\r' => res.push(c),
+			c if c < ' ' => {}
+			_ => res.push(c),
+		}
+	}
+	res
+}
+
+/// Dublin Core title, creator, subject and description, which DOCX and ODT both use for document metadata.
+/// There is no creation date, so the same input always gives the same package
+pub fn dublin_core(basics: &Basics) -> String {
+	let name = basics.name.as_deref();
+	let mut res = format!("<dc:title>{}</dc:title>\n", escape(name.unwrap_or("Resume")));
+	let fields = [
+		("creator", name),
+		("subject", basics.label.as_deref()),
+		("description", basics.summary.as_deref()),
+	];
+	for (field, value) in fields {
+		if let Some(value) = value {
+			res.push_str(&format!("<dc:{0}>{1}</dc:{0}>\n", field, escape(value)));
+		}
+	}
+	res
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn escape_markup_and_control_characters() {
+		assert_eq!(
+			escape(r#"<a href="x">Tom & 'Jerry'</a>"#),
+			"&lt;a href=&quot;x&quot;&gt;Tom &amp; &apos;Jerry&apos;&lt;/a&gt;"
+		);
+		assert_eq!(escape("tab\tline\nbell\u{7}nul\0"), "tab\tline\nbellnul");
+	}
+
+	#[test]
+	fn dublin_core_skips_missing_fields() {
+		assert_eq!(dublin_core(&Basics::default()), "<dc:title>Resume</dc:title>\n");
+
+		let basics = Basics {
+			name: Some(String::from("Jane & Co")),
+			label: Some(String::from("Engineer")),
+			..Default::default()
+		};
+		assert_eq!(
+			dublin_core(&basics),
+			"<dc:title>Jane &amp; Co</dc:title>\n<dc:creator>Jane &amp; Co</dc:creator>\n<dc:subject>Engineer</dc:subject>\n"
+		);
+	}
+}
@@ -0,0 +1,529 @@
+use std::{
+	cell::RefCell,
+	collections::{BTreeSet, HashSet},
+	fs,
+	path::PathBuf,
+};
+
+use anyhow::bail;
+use roxmltree::{Document, Node, NodeId};
+use rsb_schema::{Basics, BasicsLocation, BasicsProfilesItem, EducationItem, Iso8601, LanguagesItem, Resume, WorkItem};
+use rsb_template::CEFR_SKILLS;
+
+use crate::import::month_day;
+
+const NATIVE: &str = "Native speaker";
+
+/// Elements that were read. Whatever is left once the resume is built gets reported as unmapped
+#[derive(Default)]
+struct Used(RefCell<HashSet<NodeId>>);
+impl Used {
+	/// All child elements called `name`
+	fn children<'a, 'input>(&self, node: Node<'a, 'input>, name: &str) -> Vec<Node<'a, 'input>> {
+		let children: Vec<Node> = node
+			.children()
+			.filter(|child| child.is_element() && child.tag_name().name() == name)
+			.collect();
+		self.0.borrow_mut().extend(children.iter().map(Node::id));
+		children
+	}
+
+	/// The first child element called `name`
+	fn child<'a, 'input>(&self, node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+		let child = node
+			.children()
+			.find(|child| child.is_element() && child.tag_name().name() == name)?;
+		self.0.borrow_mut().insert(child.id());
+		Some(child)
+	}
+
+	/// Marks the element called `name` and everything in it as read, for parts that are not resume data
+	fn skip(&self, node: Node, name: &str) {
+		if let Some(node) = self.child(node, name) {
+			self.0.borrow_mut().extend(node.descendants().map(|node| node.id()));
+		}
+	}
+
+	/// Follows `path` down from `node`, e.g. `["Employer", "Name"]`
+	fn find<'a, 'input>(&self, node: Node<'a, 'input>, path: &[&str]) -> Option<Node<'a, 'input>> {
+		path.iter().try_fold(node, |node, name| self.child(node, name))
+	}
+
+	/// Text of the element at `path`, `None` when it is missing or blank
+	fn text(&self, node: Node, path: &[&str]) -> Option<String> {
+		let text = self.find(node, path)?.text()?.trim();
+		match text.is_empty() {
+			true => None,
+			false => Some(text.to_string()),
+		}
+	}
+
+	/// Paths of elements with content that were never read, below the ones that were
+	fn unmapped(&self, node: Node, path: &str, out: &mut BTreeSet<String>) {
+		for child in node.children().filter(Node::is_element) {
+			let path = match path.is_empty() {
+				true => child.tag_name().name().to_string(),
+				false => format!("{}/{}", path, child.tag_name().name()),
+			};
+			if self.0.borrow().contains(&child.id()) {
+				self.unmapped(child, &path, out);
+			} else if has_content(child) {
+				out.insert(path);
+			}
+		}
+	}
+}
+
+fn has_content(node: Node) -> bool {
+	node.attributes().len() > 0
+		|| node
+			.descendants()
+			.any(|child| child.is_text() && !child.text().unwrap_or_default().trim().is_empty())
+}
+
+/// Reads the XML Schema partial dates Europass uses, e.g. `<From year="2020" month="--01"/>`
+fn date(used: &Used, node: Node, name: &str) -> Option<Iso8601> {
+	let node = used.child(node, name)?;
+	let part = |name: &str| {
+		node.attribute(name)
+			.map(|value| value.trim_start_matches('-').parse::<u32>().ok())
+	};
+	let Some(year) = part("year").flatten().and_then(|year| u16::try_from(year).ok()) else {
+		log::warn!("{} has no year. Skipping", name);
+		return None;
+	};
+
+	let parsed = match (part("month"), part("day")) {
+		(Some(Some(month)), Some(Some(day))) => month_day(year, month, day).map(|(m, d)| Iso8601::Full(year, m, d)),
+		(Some(Some(month)), None) => month_day(year, month, 1).map(|(m, _)| Iso8601::YearMonth(year, m)),
+		(None, None) => Some(Iso8601::Year(year)),
+		_ => None,
+	};
+	if parsed.is_none() {
+		log::warn!("{} has an invalid month or day. Skipping", name);
+	}
+	parsed
+}
+
+fn period(used: &Used, node: Node) -> (Option<Iso8601>, Option<Iso8601>) {
+	match used.child(node, "Period") {
+		Some(period) => {
+			// an open period is the same as no end date
+			used.child(period, "Current");
+			(date(used, period, "From"), date(used, period, "To"))
+		}
+		None => (None, None),
+	}
+}
+
+/// A label, or its code when there is no label. Both say the same, so the other one counts as read
+fn label(used: &Used, node: Option<Node>) -> Option<String> {
+	let node = node?;
+	let label = used.text(node, &["Label"]);
+	let code = used.text(node, &["Code"]);
+	label.or(code)
+}
+
+/// A code, for fields that hold codes. The label only names what the code already stands for, so it counts as read
+fn code(used: &Used, node: Option<Node>) -> Option<String> {
+	let node = node?;
+	let code = used.text(node, &["Code"]);
+	if code.is_some() {
+		used.text(node, &["Label"]);
+	}
+	code
+}
+
+/// Europass keeps descriptions as HTML. Splits it into the text and the list items, both without markup
+fn rich_text(html: Option<String>) -> (Option<String>, Vec<String>) {
+	let Some(html) = html else {
+		return (None, Vec::new());
+	};
+
+	let mut text = String::new();
+	let mut items = Vec::new();
+	let mut item: Option<String> = None;
+	let mut rest = html.as_str();
+	while let Some((content, tag)) = rest.split_once('<') {
+		let Some((tag, after)) = tag.split_once('>') else {
+			break;
+		};
+		match item.as_mut() {
+			Some(item) => item.push_str(content),
+			None => text.push_str(content),
+		}
+
+		let tag = tag.trim().to_lowercase();
+		let name: String = tag
+			.trim_start_matches('/')
+			.chars()
+			.take_while(char::is_ascii_alphanumeric)
+			.collect();
+		match (tag.starts_with('/'), name.as_str()) {
+			(false, "li") => item = Some(String::new()),
+			(true, "li") => items.extend(item.take()),
+			(_, "br") => text.push('\n'),
+			(true, "p" | "div") => text.push_str("\n\n"),
+			_ => {}
+		}
+		rest = after;
+	}
+	text.push_str(rest);
+
+	let clean = |value: &str| {
+		let value = html_escape::decode_html_entities(value);
+		let lines: Vec<&str> = value.lines().map(str::trim).collect();
+		lines
+			.join("\n")
+			.split("\n\n")
+			.map(str::trim)
+			.filter(|p| !p.is_empty())
+			.collect::<Vec<_>>()
+			.join("\n\n")
+	};
+	let text = clean(&text);
+	let items = items
+		.iter()
+		.map(|item| clean(item))
+		.filter(|item| !item.is_empty())
+		.collect();
+	(Some(text).filter(|text| !text.is_empty()), items)
+}
+
+fn basics(used: &Used, learner: Node) -> Basics {
+	let identification = used.child(learner, "Identification");
+	let name = identification.map(|node| {
+		[
+			used.text(node, &["PersonName", "FirstName"]),
+			used.text(node, &["PersonName", "Surname"]),
+		]
+		.into_iter()
+		.flatten()
+		.collect::<Vec<_>>()
+		.join(" ")
+	});
+	let contact = identification.and_then(|node| used.child(node, "ContactInfo"));
+	let address = contact.and_then(|node| used.find(node, &["Address", "Contact"]));
+
+	let mut websites = contact
+		.and_then(|node| used.child(node, "WebsiteList"))
+		.map(|list| {
+			used.children(list, "Website")
+				.into_iter()
+				.filter_map(|website| used.text(website, &["Contact"]))
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default()
+		.into_iter();
+	let url = websites.next();
+	let profiles = websites
+		.map(|url| BasicsProfilesItem {
+			url: Some(url),
+			..Default::default()
+		})
+		.collect();
+
+	let (label, summary) = match used.child(learner, "Headline") {
+		Some(headline) => {
+			let text = used.text(headline, &["Description", "Label"]);
+			match code(used, used.child(headline, "Type")).as_deref() {
+				Some("personal_statement") => (None, text),
+				_ => (text, None),
+			}
+		}
+		None => (None, None),
+	};
+
+	Basics {
+		email: contact.and_then(|node| used.text(node, &["Email", "Contact"])),
+		label,
+		location: BasicsLocation {
+			address: address.and_then(|node| used.text(node, &["AddressLine"])),
+			city: address.and_then(|node| used.text(node, &["Municipality"])),
+			country_code: address.and_then(|node| code(used, used.child(node, "Country"))),
+			postal_code: address.and_then(|node| used.text(node, &["PostalCode"])),
+			..Default::default()
+		},
+		name: name.filter(|name| !name.is_empty()),
+		phone: contact.and_then(|node| used.text(node, &["TelephoneList", "Telephone", "Contact"])),
+		profiles,
+		summary,
+		url,
+		..Default::default()
+	}
+}
+
+fn work(used: &Used, node: Node) -> WorkItem {
+	let (start_date, end_date) = period(used, node);
+	let (summary, highlights) = rich_text(used.text(node, &["Activities"]));
+	let employer = used.child(node, "Employer");
+	let contact = employer.and_then(|node| used.child(node, "ContactInfo"));
+	let location = contact
+		.and_then(|node| used.find(node, &["Address", "Contact"]))
+		.map(|address| {
+			[
+				used.text(address, &["Municipality"]),
+				label(used, used.child(address, "Country")),
+			]
+			.into_iter()
+			.flatten()
+			.collect::<Vec<_>>()
+			.join(", ")
+		});
+
+	WorkItem {
+		end_date,
+		highlights,
+		location: location.filter(|location| !location.is_empty()),
+		name: employer.and_then(|node| used.text(node, &["Name"])),
+		position: label(used, used.child(node, "Position")),
+		start_date,
+		summary,
+		url: contact.and_then(|node| used.text(node, &["Website", "Contact"])),
+		..Default::default()
+	}
+}
+
+fn education(used: &Used, node: Node) -> EducationItem {
+	let (start_date, end_date) = period(used, node);
+	// education has no description, so the activities are kept as courses
+	let (text, items) = rich_text(used.text(node, &["Activities"]));
+	let courses = text.into_iter().chain(items).collect();
+	let organisation = used.child(node, "Organisation");
+
+	EducationItem {
+		area: label(used, used.child(node, "Field")),
+		courses,
+		end_date,
+		institution: organisation.and_then(|node| used.text(node, &["Name"])),
+		start_date,
+		study_type: used.text(node, &["Title"]),
+		url: organisation.and_then(|node| used.text(node, &["ContactInfo", "Website", "Contact"])),
+		..Default::default()
+	}
+}
+
+/// `C1` when every skill has the same level, `Listening C1, Reading C2, ...` otherwise
+fn fluency(used: &Used, node: Node) -> Option<String> {
+	let proficiency = used.child(node, "ProficiencyLevel")?;
+	let levels: Vec<(&str, String)> = CEFR_SKILLS
+		.iter()
+		.filter_map(|(name, label)| Some((*label, used.text(proficiency, &[name])?)))
+		.collect();
+
+	match levels.first() {
+		Some((_, first)) if levels.len() == CEFR_SKILLS.len() && levels.iter().all(|(_, level)| level == first) => {
+			Some(first.clone())
+		}
+		Some(_) => Some(
+			levels
+				.iter()
+				.map(|(label, level)| format!("{} {}", label, level))
+				.collect::<Vec<_>>()
+				.join(", "),
+		),
+		None => None,
+	}
+}
+
+fn languages(used: &Used, learner: Node) -> Vec<LanguagesItem> {
+	let Some(linguistic) = used.find(learner, &["Skills", "Linguistic"]) else {
+		return Vec::new();
+	};
+
+	let mut languages = Vec::new();
+	if let Some(list) = used.child(linguistic, "MotherTongueList") {
+		for node in used.children(list, "MotherTongue") {
+			languages.push(LanguagesItem {
+				language: label(used, used.child(node, "Description")),
+				fluency: Some(NATIVE.to_string()),
+			});
+		}
+	}
+	if let Some(list) = used.child(linguistic, "ForeignLanguageList") {
+		for node in used.children(list, "ForeignLanguage") {
+			languages.push(LanguagesItem {
+				language: label(used, used.child(node, "Description")),
+				fluency: fluency(used, node),
+			});
+		}
+	}
+	languages
+}
+
+/// Reads a Europass CV. Elements that have no place in the resume are logged as warnings
+pub fn read(path: &PathBuf) -> anyhow::Result<Resume> {
+	parse(&fs::read_to_string(path)?)
+}
+
+fn parse(data: &str) -> anyhow::Result<Resume> {
+	let document = Document::parse(data)?;
+	let root = document.root_element();
+	if root.tag_name().name() != "SkillsPassport" {
+		bail!("not a Europass CV, found root element {}", root.tag_name().name());
+	}
+
+	let used = Used::default();
+	used.skip(root, "DocumentInfo");
+	used.skip(root, "PrintingPreferences");
+	let Some(learner) = used.child(root, "LearnerInfo") else {
+		bail!("Europass CV has no LearnerInfo");
+	};
+
+	let work = used
+		.child(learner, "WorkExperienceList")
+		.map(|list| {
+			used.children(list, "WorkExperience")
+				.into_iter()
+				.map(|node| work(&used, node))
+				.collect()
+		})
+		.unwrap_or_default();
+	let education = used
+		.child(learner, "EducationList")
+		.map(|list| {
+			used.children(list, "Education")
+				.into_iter()
+				.map(|node| education(&used, node))
+				.collect()
+		})
+		.unwrap_or_default();
+
+	let resume = Resume {
+		basics: basics(&used, learner),
+		work,
+		education,
+		languages: languages(&used, learner),
+		..Default::default()
+	};
+
+	let mut unmapped = BTreeSet::new();
+	used.unmapped(root, "", &mut unmapped);
+	if !unmapped.is_empty() {
+		let paths: Vec<String> = unmapped.into_iter().collect();
+		log::warn!("could not map from Europass: {}", paths.join(", "));
+	}
+	Ok(resume)
+}
+
+#[cfg(test)]
+mod tests {
+	use rsb_schema::DataType;
+	use rsb_template::{render, Format, Options};
+
+	use super::*;
+
+	fn languages_of(xml: &str) -> Vec<Option<String>> {
+		let document = Document::parse(xml).unwrap();
+		let used = Used::default();
+		used.children(document.root_element(), "ForeignLanguage")
+			.into_iter()
+			.map(|node| fluency(&used, node))
+			.collect()
+	}
+
+	fn from(attributes: &str) -> Option<Iso8601> {
+		let xml = format!("<Period><From {}/></Period>", attributes);
+		let document = Document::parse(&xml).unwrap();
+		date(&Used::default(), document.root_element(), "From")
+	}
+
+	#[test]
+	fn date_partial_forms() {
+		assert_eq!(from(r#"year="2020""#), Some(Iso8601::Year(2020)));
+		assert_eq!(from(r#"year="2020" month="--01""#), Some(Iso8601::YearMonth(2020, 1)));
+		assert_eq!(
+			from(r#"year="2020" month="--02" day="---29""#),
+			Some(Iso8601::Full(2020, 2, 29))
+		);
+	}
+
+	#[test]
+	fn date_drops_out_of_range_parts() {
+		assert_eq!(from(r#"month="--01""#), None);
+		assert_eq!(from(r#"year="2020" month="--13""#), None);
+		assert_eq!(from(r#"year="2021" month="--02" day="---29""#), None);
+		assert_eq!(from(r#"year="2020" month="--300" day="---01""#), None);
+		assert_eq!(from(r#"year="2020" day="---01""#), None);
+	}
+
+	#[test]
+	fn rich_text_splits_paragraphs_and_items() {
+		let html = "<p>First &amp; second<br/>line</p><p>Next</p><ul><li>One</li><li> <b>Two</b> </li><li></li></ul>";
+		let (text, items) = rich_text(Some(html.to_string()));
+		assert_eq!(text.as_deref(), Some("First & second\nline\n\nNext"));
+		assert_eq!(items, ["One", "Two"]);
+
+		assert_eq!(rich_text(None), (None, Vec::new()));
+		assert_eq!(rich_text(Some(String::from("<p> </p>"))), (None, Vec::new()));
+	}
+
+	#[test]
+	fn fluency_collapses_equal_levels() {
+		let all = "<ProficiencyLevel><Listening>B2</Listening><Reading>B2</Reading>\
+			<SpokenInteraction>B2</SpokenInteraction><SpokenProduction>B2</SpokenProduction>\
+			<Writing>B2</Writing></ProficiencyLevel>";
+		let some = "<ProficiencyLevel><Listening>C1</Listening><SpokenInteraction>B1</SpokenInteraction>\
+			</ProficiencyLevel>";
+		let xml = format!(
+			"<List><ForeignLanguage>{}</ForeignLanguage><ForeignLanguage>{}</ForeignLanguage>\
+			<ForeignLanguage/></List>",
+			all, some
+		);
+		assert_eq!(
+			languages_of(&xml),
+			[
+				Some(String::from("B2")),
+				Some(String::from("Listening C1, Spoken interaction B1")),
+				None
+			]
+		);
+	}
+
+	#[test]
+	fn round_trips_through_the_exporter() {
+		let resume = Resume::from_buffer(
+			r#"{
+				"basics": {
+					"name": "Jane Q Doe",
+					"label": "Engineer",
+					"email": "jane@example.com",
+					"phone": "+1 555 0100",
+					"url": "https://jane.example",
+					"location": {"address": "1 Main St", "postalCode": "12345", "city": "Springfield", "countryCode": "US"}
+				},
+				"work": [{
+					"name": "Acme & Co",
+					"position": "Developer",
+					"url": "https://acme.example",
+					"location": "Springfield",
+					"startDate": "2020-01-15",
+					"summary": "Built <things>.\n\nShipped them.",
+					"highlights": ["Fast", "Safe"]
+				}],
+				"education": [{
+					"institution": "State University",
+					"area": "Physics",
+					"studyType": "BSc",
+					"startDate": "2014",
+					"endDate": "2018-06",
+					"courses": ["Optics"]
+				}],
+				"languages": [
+					{"language": "English", "fluency": "Native speaker"},
+					{"language": "German", "fluency": "Listening C1, Reading C1, Spoken interaction B2, Spoken production B2, Writing B1"},
+					{"language": "French", "fluency": "A2"}
+				]
+			}"#,
+			DataType::Json5,
+		)
+		.unwrap();
+
+		let xml = render(resume.clone(), Format::Europass, Options::default()).unwrap();
+		let imported = parse(std::str::from_utf8(&xml).unwrap()).unwrap();
+		assert_eq!(
+			imported.to_buffer(DataType::Json5).unwrap(),
+			resume.to_buffer(DataType::Json5).unwrap()
+		);
+	}
+}
@@ -0,0 +1,520 @@
+use std::{fs::File, io::Read, path::PathBuf, str::FromStr};
+
+use anyhow::anyhow;
+use rsb_schema::{
+	Basics, BasicsLocation, BasicsProfilesItem, CertificatesItem, EducationItem, Iso8601, LanguagesItem, ProjectsItem,
+	Resume, SkillsItem, WorkItem,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use zip::ZipArchive;
+
+use crate::import::month_day;
+
+const MONTHS: [&str; 12] = [
+	"jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// `None` for blank cells, which LinkedIn writes for every field that was never filled in
+fn text(value: String) -> Option<String> {
+	let value = value.trim();
+	match value.is_empty() {
+		true => None,
+		false => Some(value.to_string()),
+	}
+}
+
+/// Only four digit years, so that `Jan 1` is not read as January of year 1
+fn year(value: &str) -> Option<u16> {
+	match value.len() {
+		4 => value.parse().ok(),
+		_ => None,
+	}
+}
+
+fn month(name: &str) -> Option<u8> {
+	let name = name.get(..3)?.to_ascii_lowercase();
+	MONTHS.iter().position(|month| *month == name).map(|i| i as u8 + 1)
+}
+
+/// Parses the date formats found in exports: `2020`, `Jan 2020`, `Jan 1, 2020` and ISO 8601
+fn date(value: &str) -> Option<Iso8601> {
+	let value = value.trim();
+	if value.is_empty() {
+		return None;
+	}
+
+	let parts: Vec<&str> = value.split([' ', ',']).filter(|part| !part.is_empty()).collect();
+	let parsed = match parts[..] {
+		[month_name, y] => match (month(month_name), year(y)) {
+			(Some(m), Some(y)) => Some(Iso8601::YearMonth(y, m)),
+			_ => None,
+		},
+		[month_name, day, y] => match (month(month_name), day.parse(), year(y)) {
+			(Some(m), Ok(d), Some(y)) => month_day(y, m.into(), d).map(|(m, d)| Iso8601::Full(y, m, d)),
+			_ => None,
+		},
+		_ => Iso8601::from_str(value).ok(),
+	};
+
+	if parsed.is_none() {
+		log::warn!("could not parse date {:?}. Skipping", value);
+	}
+	parsed
+}
+
+/// Splits list cells such as `[PERSONAL:https://a.com,COMPANY:https://b.com]`, dropping the type prefixes
+fn list(value: &str) -> Vec<String> {
+	value
+		.trim()
+		.trim_start_matches('[')
+		.trim_end_matches(']')
+		.split(',')
+		.map(|item| match item.split_once(':') {
+			Some((kind, rest)) if !rest.starts_with("//") && kind.chars().all(|c| c.is_ascii_uppercase()) => rest,
+			_ => item,
+		})
+		.filter_map(|item| text(item.to_string()))
+		.collect()
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ProfileRow {
+	#[serde(rename = "First Name")]
+	first_name: String,
+	#[serde(rename = "Last Name")]
+	last_name: String,
+	#[serde(rename = "Address")]
+	address: String,
+	#[serde(rename = "Headline")]
+	headline: String,
+	#[serde(rename = "Summary")]
+	summary: String,
+	#[serde(rename = "Zip Code")]
+	zip_code: String,
+	#[serde(rename = "Geo Location")]
+	geo_location: String,
+	#[serde(rename = "Twitter Handles")]
+	twitter_handles: String,
+	#[serde(rename = "Websites")]
+	websites: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct EmailRow {
+	#[serde(rename = "Email Address")]
+	address: String,
+	#[serde(rename = "Primary")]
+	primary: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct PhoneRow {
+	#[serde(rename = "Number")]
+	number: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct PositionRow {
+	#[serde(rename = "Company Name")]
+	company: String,
+	#[serde(rename = "Title")]
+	title: String,
+	#[serde(rename = "Description")]
+	description: String,
+	#[serde(rename = "Location")]
+	location: String,
+	#[serde(rename = "Started On")]
+	started_on: String,
+	#[serde(rename = "Finished On")]
+	finished_on: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct EducationRow {
+	#[serde(rename = "School Name")]
+	school: String,
+	#[serde(rename = "Start Date")]
+	start_date: String,
+	#[serde(rename = "End Date")]
+	end_date: String,
+	#[serde(rename = "Degree Name")]
+	degree: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SkillRow {
+	#[serde(rename = "Name")]
+	name: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CertificationRow {
+	#[serde(rename = "Name")]
+	name: String,
+	#[serde(rename = "Url")]
+	url: String,
+	#[serde(rename = "Authority")]
+	authority: String,
+	#[serde(rename = "Started On")]
+	started_on: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct LanguageRow {
+	#[serde(rename = "Name")]
+	name: String,
+	#[serde(rename = "Proficiency")]
+	proficiency: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ProjectRow {
+	#[serde(rename = "Title")]
+	title: String,
+	#[serde(rename = "Description")]
+	description: String,
+	#[serde(rename = "Url")]
+	url: String,
+	#[serde(rename = "Started On")]
+	started_on: String,
+	#[serde(rename = "Finished On")]
+	finished_on: String,
+}
+
+struct Archive(ZipArchive<File>);
+impl Archive {
+	/// Rows of the CSV file called `name`, wherever it sits in the archive. A missing file has no rows
+	fn rows<T: DeserializeOwned>(&mut self, name: &str) -> anyhow::Result<Vec<T>> {
+		let path = self
+			.0
+			.file_names()
+			.find(|path| path.rsplit('/').next() == Some(name))
+			.map(String::from);
+		let Some(path) = path else {
+			log::info!("{} not found in archive. Skipping", name);
+			return Ok(Vec::new());
+		};
+
+		let mut data = String::new();
+		self.0.by_name(&path)?.read_to_string(&mut data)?;
+		let mut reader = csv::ReaderBuilder::new()
+			.flexible(true)
+			.trim(csv::Trim::All)
+			.from_reader(data.trim_start_matches('\u{feff}').as_bytes());
+
+		let rows = reader
+			.deserialize()
+			.collect::<Result<Vec<T>, _>>()
+			.map_err(|err| anyhow!("could not read {}: {}", name, err))?;
+		log::debug!("read {} rows from {}", rows.len(), name);
+		Ok(rows)
+	}
+}
+
+fn basics(profile: ProfileRow, emails: Vec<EmailRow>, phones: Vec<PhoneRow>) -> Basics {
+	let name = [profile.first_name.trim(), profile.last_name.trim()]
+		.into_iter()
+		.filter(|part| !part.is_empty())
+		.collect::<Vec<_>>()
+		.join(" ");
+
+	// e.g. `Berlin, Berlin, Germany`. Only the city maps onto a field, the country is not a code
+	let city = profile.geo_location.split(',').next().unwrap_or_default().to_string();
+
+	let email = emails
+		.iter()
+		.find(|email| email.primary.eq_ignore_ascii_case("yes"))
+		.or(emails.first())
+		.and_then(|email| text(email.address.clone()));
+
+	let mut websites = list(&profile.websites).into_iter();
+	let url = websites.next();
+	let mut profiles: Vec<BasicsProfilesItem> = websites
+		.map(|url| BasicsProfilesItem {
+			url: Some(url),
+			..Default::default()
+		})
+		.collect();
+	profiles.extend(list(&profile.twitter_handles).into_iter().map(|handle| {
+		let handle = handle.trim_start_matches('@').to_string();
+		BasicsProfilesItem {
+			network: Some(String::from("Twitter")),
+			url: Some(format!("https://twitter.com/{}", handle)),
+			username: Some(handle),
+		}
+	}));
+
+	Basics {
+		email,
+		label: text(profile.headline),
+		location: BasicsLocation {
+			address: text(profile.address),
+			city: text(city),
+			postal_code: text(profile.zip_code),
+			..Default::default()
+		},
+		name: text(name),
+		phone: phones.into_iter().find_map(|phone| text(phone.number)),
+		profiles,
+		summary: text(profile.summary),
+		url,
+		..Default::default()
+	}
+}
+
+/// Reads a LinkedIn "download your data" archive
+pub fn read(path: &PathBuf) -> anyhow::Result<Resume> {
+	let mut archive = Archive(ZipArchive::new(File::open(path)?)?);
+
+	let profile = archive
+		.rows::<ProfileRow>("Profile.csv")?
+		.into_iter()
+		.next()
+		.unwrap_or_default();
+	let emails = archive.rows("Email Addresses.csv")?;
+	let phones = archive.rows("PhoneNumbers.csv")?;
+
+	let work = archive
+		.rows::<PositionRow>("Positions.csv")?
+		.into_iter()
+		.map(|row| WorkItem {
+			name: text(row.company),
+			position: text(row.title),
+			summary: text(row.description),
+			location: text(row.location),
+			start_date: date(&row.started_on),
+			end_date: date(&row.finished_on),
+			..Default::default()
+		})
+		.collect();
+
+	let education = archive
+		.rows::<EducationRow>("Education.csv")?
+		.into_iter()
+		.map(|row| EducationItem {
+			institution: text(row.school),
+			study_type: text(row.degree),
+			start_date: date(&row.start_date),
+			end_date: date(&row.end_date),
+			..Default::default()
+		})
+		.collect();
+
+	let skills = archive
+		.rows::<SkillRow>("Skills.csv")?
+		.into_iter()
+		.map(|row| SkillsItem {
+			name: text(row.name),
+			..Default::default()
+		})
+		.collect();
+
+	let certificates = archive
+		.rows::<CertificationRow>("Certifications.csv")?
+		.into_iter()
+		.map(|row| CertificatesItem {
+			name: text(row.name),
+			url: text(row.url),
+			issuer: text(row.authority),
+			date: date(&row.started_on),
+		})
+		.collect();
+
+	let languages = archive
+		.rows::<LanguageRow>("Languages.csv")?
+		.into_iter()
+		.map(|row| LanguagesItem {
+			language: text(row.name),
+			fluency: text(row.proficiency),
+		})
+		.collect();
+
+	let projects = archive
+		.rows::<ProjectRow>("Projects.csv")?
+		.into_iter()
+		.map(|row| ProjectsItem {
+			name: text(row.title),
+			description: text(row.description),
+			url: text(row.url),
+			start_date: date(&row.started_on),
+			end_date: date(&row.finished_on),
+			..Default::default()
+		})
+		.collect();
+
+	Ok(Resume {
+		basics: basics(profile, emails, phones),
+		work,
+		education,
+		skills,
+		certificates,
+		languages,
+		projects,
+		..Default::default()
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn date_formats() {
+		assert_eq!(date("2020"), Some(Iso8601::Year(2020)));
+		assert_eq!(date("Jan 2020"), Some(Iso8601::YearMonth(2020, 1)));
+		assert_eq!(date("September 2019"), Some(Iso8601::YearMonth(2019, 9)));
+		assert_eq!(date("Mar 4, 2021"), Some(Iso8601::Full(2021, 3, 4)));
+		assert_eq!(date("2021-03-04"), Some(Iso8601::Full(2021, 3, 4)));
+		assert_eq!(date(" "), None);
+	}
+
+	#[test]
+	fn date_rejects_impossible_days() {
+		assert_eq!(date("Jan 45, 2020"), None);
+		assert_eq!(date("Feb 29, 2021"), None);
+		assert_eq!(date("Feb 29, 2020"), Some(Iso8601::Full(2020, 2, 29)));
+		assert_eq!(date("Jan 1"), None);
+		assert_eq!(date("Foo 2020"), None);
+	}
+
+	#[test]
+	fn list_drops_type_prefixes() {
+		assert_eq!(
+			list("[PERSONAL:https://a.com,COMPANY:https://b.com]"),
+			["https://a.com", "https://b.com"]
+		);
+		assert_eq!(list("https://a.com, ,b"), ["https://a.com", "b"]);
+		assert!(list("").is_empty());
+	}
+
+	/// Writes `files` into a zip under the temporary directory, the way LinkedIn nests them in a folder
+	fn archive(name: &str, files: &[(&str, &str)]) -> PathBuf {
+		use std::io::Write;
+		use zip::{write::SimpleFileOptions, ZipWriter};
+
+		let path = std::env::temp_dir().join(format!("rsb-{}-{}.zip", name, std::process::id()));
+		let mut zip = ZipWriter::new(File::create(&path).unwrap());
+		for (file, data) in files {
+			zip.start_file(
+				format!("Basic_LinkedInDataExport/{}", file),
+				SimpleFileOptions::default(),
+			)
+			.unwrap();
+			zip.write_all(data.as_bytes()).unwrap();
+		}
+		zip.finish().unwrap();
+		path
+	}
+
+	#[test]
+	fn rows_strip_the_bom_and_skip_missing_files() {
+		let path = archive("rows", &[("Skills.csv", "\u{feff}Name\nRust\n Typst \n")]);
+		let mut archive = Archive(ZipArchive::new(File::open(&path).unwrap()).unwrap());
+		let skills = archive.rows::<SkillRow>("Skills.csv").unwrap();
+		let missing = archive.rows::<SkillRow>("Languages.csv").unwrap();
+		std::fs::remove_file(path).unwrap();
+
+		assert_eq!(
+			skills.iter().map(|row| row.name.as_str()).collect::<Vec<_>>(),
+			["Rust", "Typst"]
+		);
+		assert!(missing.is_empty());
+	}
+
+	#[test]
+	fn read_maps_the_export() {
+		let path = archive(
+			"read",
+			&[
+				(
+					"Profile.csv",
+					"\u{feff}First Name,Last Name,Headline,Geo Location,Twitter Handles,Websites\n\
+					 Ada,Lovelace,Analyst,\"London, England, United Kingdom\",\"[@ada,countess]\",\
+					 \"[PERSONAL:https://ada.dev,BLOG:https://ada.blog]\"\n",
+				),
+				(
+					"Email Addresses.csv",
+					"Email Address,Confirmed,Primary\nold@ada.dev,Yes,No\nada@ada.dev,Yes,Yes\n",
+				),
+				(
+					"Positions.csv",
+					"Company Name,Title,Description,Location,Started On,Finished On\n\
+					 Analytical Engines,Programmer,Notes on the engine,London,Jan 1842,Sep 1843\n",
+				),
+				(
+					"Education.csv",
+					"School Name,Start Date,End Date,Notes,Degree Name,Activities\nHome,1830,1835,,Mathematics,\n",
+				),
+				(
+					"Certifications.csv",
+					"Name,Url,Authority,Started On,Finished On,License Number\n\
+					 Bernoulli numbers,https://ada.dev/note-g,Royal Society,\"Jul 5, 1843\",,\n",
+				),
+			],
+		);
+		let resume = read(&path).unwrap();
+		std::fs::remove_file(path).unwrap();
+
+		let basics = resume.basics;
+		assert_eq!(basics.name.as_deref(), Some("Ada Lovelace"));
+		assert_eq!(basics.label.as_deref(), Some("Analyst"));
+		assert_eq!(basics.email.as_deref(), Some("ada@ada.dev"));
+		assert_eq!(basics.location.city.as_deref(), Some("London"));
+		assert_eq!(basics.url.as_deref(), Some("https://ada.dev"));
+		let profiles = basics
+			.profiles
+			.iter()
+			.map(|profile| {
+				(
+					profile.network.as_deref(),
+					profile.username.as_deref(),
+					profile.url.as_deref(),
+				)
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(
+			profiles,
+			[
+				(None, None, Some("https://ada.blog")),
+				(Some("Twitter"), Some("ada"), Some("https://twitter.com/ada")),
+				(Some("Twitter"), Some("countess"), Some("https://twitter.com/countess")),
+			]
+		);
+
+		let [work] = resume.work.as_slice() else {
+			panic!("expected one position, got {:?}", resume.work);
+		};
+		assert_eq!(work.name.as_deref(), Some("Analytical Engines"));
+		assert_eq!(work.position.as_deref(), Some("Programmer"));
+		assert_eq!(work.summary.as_deref(), Some("Notes on the engine"));
+		assert_eq!(work.start_date, Some(Iso8601::YearMonth(1842, 1)));
+		assert_eq!(work.end_date, Some(Iso8601::YearMonth(1843, 9)));
+
+		let [education] = resume.education.as_slice() else {
+			panic!("expected one school, got {:?}", resume.education);
+		};
+		assert_eq!(education.institution.as_deref(), Some("Home"));
+		assert_eq!(education.study_type.as_deref(), Some("Mathematics"));
+		assert_eq!(education.start_date, Some(Iso8601::Year(1830)));
+		assert_eq!(education.end_date, Some(Iso8601::Year(1835)));
+
+		let [certificate] = resume.certificates.as_slice() else {
+			panic!("expected one certification, got {:?}", resume.certificates);
+		};
+		assert_eq!(certificate.name.as_deref(), Some("Bernoulli numbers"));
+		assert_eq!(certificate.issuer.as_deref(), Some("Royal Society"));
+		assert_eq!(certificate.url.as_deref(), Some("https://ada.dev/note-g"));
+		assert_eq!(certificate.date, Some(Iso8601::Full(1843, 7, 5)));
+
+		assert!(resume.skills.is_empty());
+	}
+}
@@ -0,0 +1,93 @@
+mod europass;
+mod linkedin;
+
+use std::{
+	io::{self, Write},
+	path::PathBuf,
+	str::FromStr,
+};
+
+use chrono::NaiveDate;
+use clap::{Args, Subcommand};
+use rsb_schema::{DataType, Resume};
+
+use crate::write_file;
+
+/// Services that resume data can be imported from
+#[derive(Debug, Subcommand)]
+pub enum Source {
+	#[command(about = "read a LinkedIn \"download your data\" archive")]
+	Linkedin {
+		#[arg(value_name = "ZIP_PATH", help = "file path for the downloaded archive", value_parser = PathBuf::from_str)]
+		path: PathBuf,
+		#[command(flatten)]
+		output: Output,
+	},
+	#[command(about = "read a Europass CV in XML")]
+	Europass {
+		#[arg(value_name = "XML_PATH", help = "file path for the Europass CV", value_parser = PathBuf::from_str)]
+		path: PathBuf,
+		#[command(flatten)]
+		output: Output,
+	},
+}
+
+/// Where imported data is written, and in which data type
+#[derive(Debug, Clone, Args)]
+pub struct Output {
+	#[arg(
+		short,
+		long,
+		value_name = "OUTPUT_PATH",
+		help = "write data to this file instead of stdout. Data type is inferred from the extension unless set",
+		value_parser = PathBuf::from_str
+	)]
+	pub output: Option<PathBuf>,
+	#[arg(
+		short,
+		long,
+		value_name = "OUTPUT_FORMAT",
+		help = "data type to write. Inferred from OUTPUT_PATH when not set, json5 otherwise",
+		value_parser = DataType::from_str
+	)]
+	pub format: Option<DataType>,
+}
+
+impl Output {
+	pub fn write(&self, resume: &Resume) -> anyhow::Result<()> {
+		let data_type = match (self.format, &self.output) {
+			(Some(data_type), _) => data_type,
+			(None, Some(out_path)) => DataType::from_path(out_path)?,
+			(None, None) => DataType::Json5,
+		};
+		let data = resume.to_buffer(data_type)?;
+
+		match &self.output {
+			Some(out_path) => write_file(out_path, data),
+			None => Ok(io::stdout().write_all(data.as_bytes())?),
+		}
+	}
+}
+
+/// `month` and `day` narrowed to `u8`, if they make a real date in `year`
+fn month_day(year: u16, month: u32, day: u32) -> Option<(u8, u8)> {
+	NaiveDate::from_ymd_opt(year.into(), month, day)?;
+	Some((u8::try_from(month).ok()?, u8::try_from(day).ok()?))
+}
+
+impl Source {
+	pub fn handle(&self) -> anyhow::Result<()> {
+		match self {
+			Source::Linkedin { path, output } => {
+				log::debug!("Importing LinkedIn archive: {:?}", path);
+				let resume = linkedin::read(path)?;
+				output.write(&resume)
+			}
+			Source::Europass { path, output } => {
+				log::debug!("Importing Europass CV: {:?}", path);
+				let resume = europass::read(path)?;
+				output.write(&resume)
+			}
+		}
+	}
+}
@@ -0,0 +1,123 @@
+use std::{
+	fs,
+	io::{self, Read},
+	path::PathBuf,
+	str::FromStr,
+};
+
+use clap::Args;
+use rsb_schema::{DataType, Resume};
+#[cfg(feature = "jsonnet")]
+use rsb_schema::{JsonnetOptions, DEFAULT_MAX_STACK, DEFAULT_MAX_TRACE};
+
+use crate::config::STDIN_PATH;
+
+/// Where resume data is read from, and how to parse it
+#[derive(Debug, Clone, Args)]
+pub struct Input {
+	#[arg(value_name = "INPUT_PATH", help = "file path for data, or - for stdin", value_parser = PathBuf::from_str)]
+	pub path: PathBuf,
+	#[arg(
+		short,
+		long,
+		value_name = "INPUT_FORMAT",
+		help = "format of the input data. Inferred from the extension, or the content for stdin, when not set",
+		value_parser = DataType::from_str
+	)]
+	pub input_format: Option<DataType>,
+	#[cfg(feature = "jsonnet")]
+	#[command(flatten)]
+	pub jsonnet: JsonnetArgs,
+}
+
+/// Splits `KEY=VALUE`. A bare `KEY` takes its value from the environment, like the jsonnet CLI does
+#[cfg(feature = "jsonnet")]
+fn parse_var(value: &str) -> anyhow::Result<(String, String)> {
+	match value.split_once('=') {
+		Some((key, value)) => Ok((key.to_string(), value.to_string())),
+		None => {
+			let env = std::env::var(value).map_err(|err| anyhow::anyhow!("{}: {}", value, err))?;
+			Ok((value.to_string(), env))
+		}
+	}
+}
+
+#[cfg(feature = "jsonnet")]
+#[derive(Debug, Clone, Args)]
+pub struct JsonnetArgs {
+	#[arg(
+		long = "ext-str",
+		value_name = "KEY[=VALUE]",
+		help = "jsonnet external variable, read with std.extVar",
+		value_parser = parse_var
+	)]
+	pub ext_strs: Vec<(String, String)>,
+	#[arg(
+		long = "tla-str",
+		value_name = "KEY[=VALUE]",
+		help = "jsonnet top-level argument, for sources that evaluate to a function",
+		value_parser = parse_var
+	)]
+	pub tla_strs: Vec<(String, String)>,
+	#[arg(
+		short = 'J',
+		long = "jpath",
+		value_name = "DIR",
+		help = "jsonnet library search path. Can be repeated",
+		value_parser = PathBuf::from_str
+	)]
+	pub library_paths: Vec<PathBuf>,
+	#[arg(long, help = "jsonnet maximum stack depth", default_value_t = DEFAULT_MAX_STACK)]
+	pub max_stack: u32,
+	#[arg(long, help = "jsonnet maximum stack trace lines in errors. 0 for no limit", default_value_t = DEFAULT_MAX_TRACE)]
+	pub max_trace: u32,
+}
+
+#[cfg(feature = "jsonnet")]
+impl From<&JsonnetArgs> for JsonnetOptions {
+	fn from(args: &JsonnetArgs) -> Self {
+		JsonnetOptions {
+			ext_strs: args.ext_strs.clone(),
+			tla_strs: args.tla_strs.clone(),
+			library_paths: args.library_paths.clone(),
+			max_stack: args.max_stack,
+			max_trace: Some(args.max_trace).filter(|limit| *limit > 0),
+		}
+	}
+}
+
+impl Input {
+	pub fn is_stdin(&self) -> bool {
+		self.path.as_os_str() == STDIN_PATH
+	}
+
+	/// Reads the resume. An explicit format wins over the extension. Only stdin, which has no extension, is sniffed
+	pub fn read(&self) -> anyhow::Result<Resume> {
+		#[cfg(feature = "jsonnet")]
+		if !self.is_stdin() {
+			let by_extension = self.input_format.is_none() && self.path.extension().is_some_and(|ext| ext == "jsonnet");
+			if by_extension || self.input_format == Some(DataType::Jsonnet) {
+				return Resume::from_jsonnet_file(&self.path, &(&self.jsonnet).into());
+			}
+		}
+
+		let data = match (self.is_stdin(), self.input_format) {
+			(false, None) => return Resume::from_file(&self.path),
+			(true, _) => {
+				let mut data = String::new();
+				io::stdin().read_to_string(&mut data)?;
+				data
+			}
+			(false, Some(_)) => fs::read_to_string(&self.path)?,
+		};
+
+		let data_type = self.input_format.unwrap_or_else(|| DataType::sniff(&data));
+		log::debug!("reading {:?} as {:?}", self.path, data_type);
+
+		#[cfg(feature = "jsonnet")]
+		if data_type == DataType::Jsonnet {
+			return Resume::from_jsonnet_buffer(&data, &(&self.jsonnet).into());
+		}
+		Resume::from_buffer(&data, data_type)
+	}
+}
@@ -1,11 +1,20 @@
 mod config;
+mod import;
+mod input;
+mod server;
 
-use std::{fs, marker::PhantomData, net::SocketAddr, path::PathBuf, str::FromStr};
+use std::{
+	fs,
+	io::{self, Write},
+	net::SocketAddr,
+	path::PathBuf,
+	str::FromStr,
+};
 
 use clap::Parser;
 use config::{DEFAULT_CLI_HELP_PATH, DEFAULT_SERVE_ADDR};
-use rsb_schema::Resume;
-use rsb_template::generate;
+use input::Input;
+use rsb_template::{render, Format, Options};
 
 mod build {
 	pub const LONG_HELP_TEXT: &str = include_str!(concat!(env!("OUT_DIR"), "/long-help.txt"));
@@ -16,18 +25,44 @@ mod build {
 pub enum Command {
 	#[command(visible_alias = "gen", about = "generate resume from input")]
 	Generate {
-		#[arg(value_name = "INPUT_PATH", help = "file path for data", value_parser = PathBuf::from_str)]
-		path: PathBuf,
+		#[command(flatten)]
+		input: Input,
+		#[arg(
+			short,
+			long,
+			value_name = "OUTPUT_PATH",
+			help = "write output to this file instead of stdout. Format is inferred from the extension unless set",
+			value_parser = PathBuf::from_str
+		)]
+		output: Option<PathBuf>,
+		#[arg(
+			short,
+			long,
+			value_enum,
+			help = "output format. Inferred from OUTPUT_PATH when not set, html otherwise"
+		)]
+		format: Option<Format>,
+		#[command(flatten)]
+		opts: Options,
 	},
 	#[command(visible_alias = "check", about = "check input for errors")]
 	Validate {
-		#[arg(value_name = "INPUT_PATH", help = "file path for data", value_parser=PathBuf::from_str)]
-		path: PathBuf,
+		#[command(flatten)]
+		input: Input,
+	},
+	#[command(about = "convert data exported from other services into resume data")]
+	Import {
+		#[command(subcommand)]
+		source: import::Source,
 	},
 	#[command(about = "start a server for easy editing")]
 	Serve {
+		#[command(flatten)]
+		input: Input,
 		#[arg(help = "bind address for the server", value_parser = SocketAddr::from_str, default_value_t = DEFAULT_SERVE_ADDR)]
 		address: SocketAddr,
+		#[command(flatten)]
+		opts: Options,
 	},
 	#[clap(hide = true)]
 	GenerateMarkdownHelp {
@@ -36,36 +71,55 @@ pub enum Command {
 	},
 }
 
+fn write_file(path: &PathBuf, data: impl AsRef<[u8]>) -> anyhow::Result<()> {
+	let parent = path
+		.parent()
+		.ok_or_else(|| anyhow::anyhow!("Could not find base path for file: {:?}", path))?;
+
+	fs::create_dir_all(parent)?;
+	Ok(fs::write(path, data)?)
+}
+
 impl Command {
 	pub fn handle_cmd(&self) -> anyhow::Result<()> {
 		match self {
-			Command::Generate { path, .. } => {
-				log::debug!("Running generate with path: {:?}", path);
-				let resume_data = Resume::from_file(path)?;
+			Command::Generate {
+				input,
+				output,
+				format,
+				opts,
+			} => {
+				log::debug!("Running generate with path: {:?} and output: {:?}", input.path, output);
+				let format = match (format, output) {
+					(Some(format), _) => *format,
+					(None, Some(out_path)) => Format::from_path(out_path)?,
+					(None, None) => Format::default(),
+				};
+				let resume_data = input.read()?;
 				log::debug!("found data: {}", resume_data);
-				println!("{}", generate(resume_data)?);
-				Ok(())
+				let rendered = render(resume_data, format, opts.clone())?;
+
+				match output {
+					Some(out_path) => write_file(out_path, rendered),
+					None => Ok(io::stdout().write_all(&rendered)?),
+				}
 			}
-			Command::Validate { path } => {
-				log::debug!("Running validate with path: {:?}", path);
-				let resume_data = Resume::from_file(path)?;
+			Command::Validate { input } => {
+				log::debug!("Running validate with path: {:?}", input.path);
+				let resume_data = input.read()?;
 				log::debug!("found data: {}", resume_data);
 				Ok(())
 			}
+			Command::Import { source } => source.handle(),
 			Command::GenerateMarkdownHelp { path } => {
 				log::debug!("Running md_help_gen with out path: {:?}", path);
 				let md_opts = clap_markdown::MarkdownOptions::new().show_footer(false);
 				let md_str = clap_markdown::help_markdown_custom::<Self>(&md_opts);
-				let parent = path
-					.parent()
-					.ok_or_else(|| anyhow::anyhow!("Could not find base path for file: {:?}", path))?;
-
-				fs::create_dir_all(parent)?;
-				Ok(fs::write(path, md_str)?)
+				write_file(path, md_str)
 			}
-			Command::Serve { address } => {
-				log::debug!("Running serve with address: {}", address);
-				todo!("server handler"); // Add leptos and axum entrypoints here
+			Command::Serve { input, address, opts } => {
+				log::debug!("Running serve with path: {:?} and address: {}", input.path, address);
+				server::serve(input, *address, opts)
 			}
 		}
 	}
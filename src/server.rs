@@ -0,0 +1,80 @@
+use std::net::SocketAddr;
+
+use anyhow::{anyhow, bail};
+use rsb_template::{generate, Options};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::input::Input;
+
+const HTML_CONTENT_TYPE: &str = "text/html; charset=utf-8";
+const TEXT_CONTENT_TYPE: &str = "text/plain; charset=utf-8";
+
+fn content_type(value: &str) -> Header {
+	Header::from_bytes("Content-Type", value).expect("content type header must be valid")
+}
+
+fn render(input: &Input, opts: &Options) -> anyhow::Result<String> {
+	let resume_data = input.read()?;
+	log::debug!("found data: {}", resume_data);
+	generate(resume_data, opts.clone())
+}
+
+/// The path of a request URL, without the query string that cache-busting reloads add
+fn url_path(url: &str) -> &str {
+	url.split(['?', '#']).next().unwrap_or_default()
+}
+
+fn handle_request(request: Request, input: &Input, opts: &Options) -> anyhow::Result<()> {
+	log::info!("{} {}", request.method(), request.url());
+
+	let response = match (request.method(), url_path(request.url())) {
+		(Method::Get, "/") => match render(input, opts) {
+			Ok(html) => Response::from_string(html).with_header(content_type(HTML_CONTENT_TYPE)),
+			Err(err) => {
+				log::error!("could not render {:?}: {:#}", input.path, err);
+				Response::from_string(format!("could not render {:?}: {:#}", input.path, err))
+					.with_header(content_type(TEXT_CONTENT_TYPE))
+					.with_status_code(500)
+			}
+		},
+		(Method::Get, _) => Response::from_string("not found")
+			.with_header(content_type(TEXT_CONTENT_TYPE))
+			.with_status_code(404),
+		_ => Response::from_string("method not allowed")
+			.with_header(content_type(TEXT_CONTENT_TYPE))
+			.with_status_code(405),
+	};
+
+	Ok(request.respond(response)?)
+}
+
+/// Serve the resume from `input` on `address`, re-reading the input on every request
+pub fn serve(input: &Input, address: SocketAddr, opts: &Options) -> anyhow::Result<()> {
+	if input.is_stdin() {
+		bail!("serve re-reads the input on every request, so it can not read from stdin");
+	}
+
+	let server = Server::http(address).map_err(|e| anyhow!("could not bind to {}: {}", address, e))?;
+	log::info!("serving {:?} on http://{}", input.path, address);
+
+	for request in server.incoming_requests() {
+		if let Err(err) = handle_request(request, input, opts) {
+			log::error!("could not respond to request: {}", err);
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::url_path;
+
+	#[test]
+	fn url_path_drops_query_string() {
+		assert_eq!(url_path("/"), "/");
+		assert_eq!(url_path("/?x=1"), "/");
+		assert_eq!(url_path("/#top"), "/");
+		assert_eq!(url_path("/other?x=1"), "/other");
+	}
+}